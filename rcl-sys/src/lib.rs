@@ -22,6 +22,8 @@ pub mod rcutils;
 pub use rcutils::*;
 
 pub mod rmw;
+// Both of rcl and rmw have `init_options`, and `rcl_sys::init_options` is rcl's one.
+pub use rcl::init_options;
 pub use rmw::*;
 
 pub mod rosidl_runtime_c;
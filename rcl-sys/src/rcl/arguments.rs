@@ -1,5 +1,7 @@
 //! API in rcl/arguments.h
 
+use std::os::raw::{c_char, c_int};
+
 use crate::*;
 
 #[repr(C)]
@@ -17,9 +19,66 @@ pub struct rcl_arguments_t {
 }
 
 extern "C" {
+    /// Return a rcl_arguments_t struct with members initialized to `NULL`.
+    pub fn rcl_get_zero_initialized_arguments() -> rcl_arguments_t;
+
+    /// Parse command line arguments into a structure usable by code.
+    pub fn rcl_parse_arguments(
+        argc: c_int,
+        argv: *const *const c_char,
+        allocator: rcl_allocator_t,
+        args_output: *mut rcl_arguments_t,
+    ) -> rcl_ret_t;
+
+    /// Return the number of arguments that were not ROS specific arguments.
+    pub fn rcl_arguments_get_count_unparsed(args: *const rcl_arguments_t) -> c_int;
+
+    /// Return a list of indices to non ROS specific arguments.
+    pub fn rcl_arguments_get_unparsed(
+        args: *const rcl_arguments_t,
+        allocator: rcl_allocator_t,
+        output_unparsed_indices: *mut *mut c_int,
+    ) -> rcl_ret_t;
+
+    /// Return the number of ROS specific arguments that were not successfully parsed.
+    pub fn rcl_arguments_get_count_unparsed_ros(args: *const rcl_arguments_t) -> c_int;
+
+    /// Return a list of indices to unknown ROS specific arguments that were left unparsed.
+    pub fn rcl_arguments_get_unparsed_ros(
+        args: *const rcl_arguments_t,
+        allocator: rcl_allocator_t,
+        output_unparsed_ros_indices: *mut *mut c_int,
+    ) -> rcl_ret_t;
+
+    /// Return the number of parameter yaml files given in the arguments.
+    pub fn rcl_arguments_get_param_files_count(args: *const rcl_arguments_t) -> c_int;
+
+    /// Return a list of yaml parameter file paths specified on the command line.
+    pub fn rcl_arguments_get_param_files(
+        arguments: *const rcl_arguments_t,
+        allocator: rcl_allocator_t,
+        parameter_files: *mut *mut *mut c_char,
+    ) -> rcl_ret_t;
+
     /// Return all parameter overrides parsed from the command line.
     pub fn rcl_arguments_get_param_overrides(
         arguments: *const rcl_arguments_t,
         parameter_overrides: *mut *mut rcl_params_t,
     ) -> rcl_ret_t;
+
+    /// Return log levels parsed from the command line.
+    #[cfg(feature = "galactic+")]
+    pub fn rcl_arguments_get_log_levels(
+        arguments: *const rcl_arguments_t,
+        log_levels: *mut rcl_log_levels_t,
+    ) -> rcl_ret_t;
+
+    /// Copy one arguments structure into another.
+    pub fn rcl_arguments_copy(
+        args: *const rcl_arguments_t,
+        args_out: *mut rcl_arguments_t,
+    ) -> rcl_ret_t;
+
+    /// Reclaim resources held inside rcl_arguments_t structure.
+    pub fn rcl_arguments_fini(args: *mut rcl_arguments_t) -> rcl_ret_t;
 }
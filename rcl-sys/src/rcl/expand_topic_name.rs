@@ -0,0 +1,10 @@
+//! API in rcl/expand_topic_name.h
+
+use crate::*;
+
+extern "C" {
+    /// Fill a given string map with the default substitution pairs.
+    pub fn rcl_get_default_topic_name_substitutions(
+        string_map: *mut rcutils_string_map_t,
+    ) -> rcl_ret_t;
+}
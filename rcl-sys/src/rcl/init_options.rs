@@ -27,4 +27,14 @@ extern "C" {
 
     /// Finalize the given init_options.
     pub fn rcl_init_options_fini(init_options: *mut rcl_init_options_t) -> rcl_ret_t;
+
+    /// Return the allocator stored in the init_options.
+    pub fn rcl_init_options_get_allocator(
+        init_options: *const rcl_init_options_t,
+    ) -> *const rcl_allocator_t;
+
+    /// Return the rmw init options which are stored internally.
+    pub fn rcl_init_options_get_rmw_init_options(
+        init_options: *mut rcl_init_options_t,
+    ) -> *mut rmw_init_options_t;
 }
@@ -0,0 +1,39 @@
+//! API in rcl/log_level.h
+
+use std::os::raw::{c_char, c_int};
+
+use crate::*;
+
+/// A logger item to specify a name and a log level.
+#[repr(C)]
+#[derive(Debug)]
+pub struct rcl_logger_setting_t {
+    /// Name for the logger.
+    pub name: *const c_char,
+    /// Minimum severity level of log messages for the logger.
+    pub level: c_int,
+}
+
+/// Hold default logger level and other logger setting.
+#[repr(C)]
+#[derive(Debug)]
+pub struct rcl_log_levels_t {
+    /// Minimum default logger level severity.
+    pub default_logger_level: c_int,
+    /// Array of logger setting.
+    pub logger_settings: *mut rcl_logger_setting_t,
+    /// Number of logger settings.
+    pub num_logger_settings: usize,
+    /// Capacity of logger settings.
+    pub capacity_logger_settings: usize,
+    /// Allocator used to allocate objects in this struct.
+    pub allocator: rcl_allocator_t,
+}
+
+extern "C" {
+    /// Return a rcl_log_levels_t struct with members initialized to zero value.
+    pub fn rcl_get_zero_initialized_log_levels() -> rcl_log_levels_t;
+
+    /// Reclaim resources held inside rcl_log_levels_t structure.
+    pub fn rcl_log_levels_fini(log_levels: *mut rcl_log_levels_t) -> rcl_ret_t;
+}
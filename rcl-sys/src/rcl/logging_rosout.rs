@@ -0,0 +1,30 @@
+//! API in rcl/logging_rosout.h
+
+use std::os::raw::{c_char, c_int};
+
+use crate::*;
+
+extern "C" {
+    /// Initialize the rcl_logging_rosout features.
+    pub fn rcl_logging_rosout_init(allocator: *const rcl_allocator_t) -> rcl_ret_t;
+
+    /// Uninitialize the rcl_logging_rosout features.
+    pub fn rcl_logging_rosout_fini() -> rcl_ret_t;
+
+    /// Create a rosout publisher for a node and register it with the logging system.
+    pub fn rcl_logging_rosout_init_publisher_for_node(node: *mut rcl_node_t) -> rcl_ret_t;
+
+    /// Deregister a rosout publisher for a node with the logging system and clean up allocated
+    /// resources.
+    pub fn rcl_logging_rosout_fini_publisher_for_node(node: *mut rcl_node_t) -> rcl_ret_t;
+
+    /// The output handler outputs log messages to rosout topics.
+    pub fn rcl_logging_rosout_output_handler(
+        location: *const rcutils_log_location_t,
+        severity: c_int,
+        name: *const c_char,
+        timestamp: rcutils_time_point_value_t,
+        format: *const c_char,
+        args: *mut va_list,
+    );
+}
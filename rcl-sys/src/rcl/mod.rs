@@ -6,26 +6,27 @@
 //! - [x] [context.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/context.h)
 //! - [ ] [error_handling.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/error_handling.h)
 //! - [x] [event.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/event.h)
-//! - [ ] [expand_topic_name.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/expand_topic_name.h)
+//! - [x] [expand_topic_name.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/expand_topic_name.h)
 //!   - `expand_topic_or_service_name.cpp`
 //! - [x] [graph.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/graph.h)
 //! - [x] [guard_condition.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/guard_condition.h)
 //! - [x] [init.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/init.h)
 //! - [x] [init_options.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/init_options.h)
+//! - [x] [log_level.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/log_level.h) (galactic+)
 //! - [x] [logging.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/logging.h)
-//! - [ ] [logging_rosout.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/logging_rosout.h)
+//! - [x] [logging_rosout.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/logging_rosout.h)
 //!   - `qos.hpp`
 //! - [x] [node.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/node.h)
 //! - [x] [node_options.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/node_options.h)
 //! - [x] [publisher.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/publisher.h)
-//! - [ ] [remap.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/remap.h)
+//! - [x] [remap.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/remap.h)
 //!   - `node_interfaces/node_graph.cpp`
 //! - [x] [service.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/service.h)
 //! - [x] [subscription.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/subscription.h)
 //! - [x] [time.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/time.h)
 //! - [x] [timer.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/timer.h)
 //! - [x] [types.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/types.h)
-//! - [ ] [validate_topic_name.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/validate_topic_name.h)
+//! - [x] [validate_topic_name.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/validate_topic_name.h)
 //!    - `expand_topic_or_service_name.cpp`
 //! - [x] [wait.h](https://github.com/ros2/rcl/blob/master/rcl/include/rcl/wait.h)
 //!
@@ -56,6 +57,9 @@ pub use context::*;
 pub mod event;
 pub use event::*;
 
+pub mod expand_topic_name;
+pub use expand_topic_name::*;
+
 pub mod graph;
 pub use graph::*;
 
@@ -68,9 +72,17 @@ pub use init::*;
 pub mod init_options;
 pub use init_options::*;
 
+#[cfg(feature = "galactic+")]
+pub mod log_level;
+#[cfg(feature = "galactic+")]
+pub use log_level::*;
+
 pub mod logging;
 pub use logging::*;
 
+pub mod logging_rosout;
+pub use logging_rosout::*;
+
 pub mod publisher;
 pub use publisher::*;
 
@@ -80,6 +92,9 @@ pub use node::*;
 pub mod node_options;
 pub use node_options::*;
 
+pub mod remap;
+pub use remap::*;
+
 pub mod service;
 pub use service::*;
 
@@ -95,5 +110,8 @@ pub use timer::*;
 pub mod types;
 pub use types::*;
 
+pub mod validate_topic_name;
+pub use validate_topic_name::*;
+
 pub mod wait;
 pub use wait::*;
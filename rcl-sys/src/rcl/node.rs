@@ -69,4 +69,14 @@ extern "C" {
 
     /// Return the logger name of the node.
     pub fn rcl_node_get_logger_name(node: *const rcl_node_t) -> *const c_char;
+
+    /// Expand a given name into a fully-qualified topic name and apply remapping rules.
+    pub fn rcl_node_resolve_name(
+        node: *const rcl_node_t,
+        input_name: *const c_char,
+        allocator: rcl_allocator_t,
+        is_service: bool,
+        only_expand: bool,
+        output_name: *mut *mut c_char,
+    ) -> rcl_ret_t;
 }
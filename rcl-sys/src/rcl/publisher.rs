@@ -95,6 +95,9 @@ extern "C" {
         publisher: *const rcl_publisher_t,
     ) -> *const rcl_publisher_options_t;
 
+    /// Return the rmw publisher handle.
+    pub fn rcl_publisher_get_rmw_handle(publisher: *const rcl_publisher_t) -> *mut rmw_publisher_t;
+
     /// Return the context associated with this publisher.
     pub fn rcl_publisher_get_context(publisher: *const rcl_publisher_t) -> *mut rcl_context_t;
 
@@ -0,0 +1,49 @@
+//! API in rcl/remap.h
+
+use std::os::raw::c_char;
+
+use crate::*;
+
+extern "C" {
+    /// Remap a topic name based on given rules.
+    pub fn rcl_remap_topic_name(
+        local_arguments: *const rcl_arguments_t,
+        global_arguments: *const rcl_arguments_t,
+        topic_name: *const c_char,
+        node_name: *const c_char,
+        node_namespace: *const c_char,
+        substitutions: *const rcutils_string_map_t,
+        allocator: rcl_allocator_t,
+        output_name: *mut *mut c_char,
+    ) -> rcl_ret_t;
+
+    /// Remap a service name based on given rules.
+    pub fn rcl_remap_service_name(
+        local_arguments: *const rcl_arguments_t,
+        global_arguments: *const rcl_arguments_t,
+        service_name: *const c_char,
+        node_name: *const c_char,
+        node_namespace: *const c_char,
+        substitutions: *const rcutils_string_map_t,
+        allocator: rcl_allocator_t,
+        output_name: *mut *mut c_char,
+    ) -> rcl_ret_t;
+
+    /// Remap a node name based on given rules.
+    pub fn rcl_remap_node_name(
+        local_arguments: *const rcl_arguments_t,
+        global_arguments: *const rcl_arguments_t,
+        node_name: *const c_char,
+        allocator: rcl_allocator_t,
+        output_name: *mut *mut c_char,
+    ) -> rcl_ret_t;
+
+    /// Remap a namespace based on given rules.
+    pub fn rcl_remap_node_namespace(
+        local_arguments: *const rcl_arguments_t,
+        global_arguments: *const rcl_arguments_t,
+        node_name: *const c_char,
+        allocator: rcl_allocator_t,
+        output_namespace: *mut *mut c_char,
+    ) -> rcl_ret_t;
+}
@@ -0,0 +1,28 @@
+//! API in rcl/validate_topic_name.h
+
+use std::os::raw::{c_char, c_int};
+
+use crate::*;
+
+pub const RCL_TOPIC_NAME_VALID: c_int = 0;
+pub const RCL_TOPIC_NAME_INVALID_IS_EMPTY_STRING: c_int = 1;
+pub const RCL_TOPIC_NAME_INVALID_ENDS_WITH_FORWARD_SLASH: c_int = 2;
+pub const RCL_TOPIC_NAME_INVALID_CONTAINS_UNALLOWED_CHARACTERS: c_int = 3;
+pub const RCL_TOPIC_NAME_INVALID_NAME_TOKEN_STARTS_WITH_NUMBER: c_int = 4;
+pub const RCL_TOPIC_NAME_INVALID_UNMATCHED_CURLY_BRACE: c_int = 5;
+pub const RCL_TOPIC_NAME_INVALID_MISPLACED_TILDE: c_int = 6;
+pub const RCL_TOPIC_NAME_INVALID_TILDE_NOT_FOLLOWED_BY_FORWARD_SLASH: c_int = 7;
+pub const RCL_TOPIC_NAME_INVALID_SUBSTITUTION_CONTAINS_UNALLOWED_CHARACTERS: c_int = 8;
+pub const RCL_TOPIC_NAME_INVALID_SUBSTITUTION_STARTS_WITH_NUMBER: c_int = 9;
+
+extern "C" {
+    /// Validate a given topic name, which may be relative or contain substitutions.
+    pub fn rcl_validate_topic_name(
+        topic_name: *const c_char,
+        validation_result: *mut c_int,
+        invalid_index: *mut usize,
+    ) -> rcl_ret_t;
+
+    /// Return a validation result description, or NULL if unknown or RCL_TOPIC_NAME_VALID.
+    pub fn rcl_topic_name_validation_result_string(validation_result: c_int) -> *const c_char;
+}
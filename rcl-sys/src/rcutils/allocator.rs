@@ -4,7 +4,7 @@ use std::os::raw::c_void;
 
 /// Encapsulation of an allocator.
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct rcutils_allocator_t {
     /// Allocate memory, given a size and the `state` pointer.
     pub allocate: Option<unsafe extern "C" fn(size: usize, state: *mut c_void) -> *mut c_void>,
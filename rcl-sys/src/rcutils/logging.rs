@@ -83,6 +83,12 @@ pub type rcutils_logging_output_handler_t = Option<
     ),
 >;
 extern "C" {
+    /// Get the current output handler.
+    pub fn rcutils_logging_get_output_handler() -> rcutils_logging_output_handler_t;
+
+    /// Set the current output handler.
+    pub fn rcutils_logging_set_output_handler(function: rcutils_logging_output_handler_t);
+
     /// Get the default level for loggers.
     pub fn rcutils_logging_get_default_logger_level() -> c_int;
 
@@ -107,6 +113,7 @@ extern "C" {
         severity: c_int,
         name: *const c_char,
         format: *const c_char,
+        ...
     );
 
     /// The default output handler outputs log messages to the standard streams.
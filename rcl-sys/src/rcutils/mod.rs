@@ -12,6 +12,20 @@ pub use logging::*;
 pub mod types;
 pub use types::*;
 
+pub mod strdup {
+    //! API in rcutils/strdup.h
+
+    use std::os::raw::c_char;
+
+    use super::rcutils_allocator_t;
+
+    extern "C" {
+        /// Return a duplicated string with an allocator, or null if an error occurs.
+        pub fn rcutils_strdup(str_: *const c_char, allocator: rcutils_allocator_t) -> *mut c_char;
+    }
+}
+pub use strdup::*;
+
 pub mod time {
     //! API in rcutils/time.h
 
@@ -79,3 +79,40 @@ extern "C" {
     /// Finalize a uint8 array struct.
     pub fn rcutils_uint8_array_fini(uint8_array: *mut rcutils_uint8_array_t) -> rcutils_ret_t;
 }
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct rcutils_char_array_t {
+    /// A pointer to the allocated memory for this string.
+    pub buffer: *mut c_char,
+    /// if this is true, we may safely free/realloc the buffer.
+    pub owns_buffer: bool,
+    /// The length of the string (including the trailing null char).
+    pub buffer_length: usize,
+    /// The maximum capacity of the allocated memory.
+    pub buffer_capacity: usize,
+    /// The allocator used to allocate and free memory for the buffer.
+    pub allocator: rcutils_allocator_t,
+}
+
+extern "C" {
+    /// Return a zero initialized char array struct.
+    pub fn rcutils_get_zero_initialized_char_array() -> rcutils_char_array_t;
+
+    /// Initialize a zero initialized char array struct.
+    pub fn rcutils_char_array_init(
+        char_array: *mut rcutils_char_array_t,
+        buffer_capacity: usize,
+        allocator: *const rcutils_allocator_t,
+    ) -> rcutils_ret_t;
+
+    /// Finalize a char array struct.
+    pub fn rcutils_char_array_fini(char_array: *mut rcutils_char_array_t) -> rcutils_ret_t;
+
+    /// Produce output with a printf-style format, without consuming `args`.
+    pub fn rcutils_char_array_vsprintf(
+        char_array: *mut rcutils_char_array_t,
+        format: *const c_char,
+        args: *mut crate::__va_list_tag,
+    ) -> rcutils_ret_t;
+}
@@ -0,0 +1,66 @@
+//! API in rmw/init_options.h, rmw/localhost.h and rmw/security_options.h
+
+use std::os::raw::c_char;
+
+use crate::rcutils::rcutils_allocator_t;
+
+/// Value of `domain_id` which uses the default domain ID, e.g. given by `ROS_DOMAIN_ID`.
+#[cfg(feature = "galactic+")]
+pub const RMW_DEFAULT_DOMAIN_ID: usize = usize::MAX;
+
+#[repr(u32)]
+/// Used to specify if the context can only communicate through localhost.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum rmw_localhost_only_t {
+    /// Uses ROS_LOCALHOST_ONLY environment variable.
+    RMW_LOCALHOST_ONLY_DEFAULT = 0,
+    /// Forces using only localhost.
+    RMW_LOCALHOST_ONLY_ENABLED = 1,
+    /// Forces disabling localhost only.
+    RMW_LOCALHOST_ONLY_DISABLED = 2,
+}
+
+#[repr(u32)]
+/// Policy of the security enforcement
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum rmw_security_enforcement_policy_t {
+    RMW_SECURITY_ENFORCEMENT_PERMISSIVE = 0,
+    RMW_SECURITY_ENFORCEMENT_ENFORCE = 1,
+}
+
+/// Options of security
+#[repr(C)]
+#[derive(Debug)]
+pub struct rmw_security_options_t {
+    pub enforce_security: rmw_security_enforcement_policy_t,
+    pub security_root_path: *mut c_char,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+struct rmw_init_options_impl_t {
+    _unused: [u8; 0],
+}
+
+/// Options structure used during rmw_init().
+#[repr(C)]
+#[derive(Debug)]
+pub struct rmw_init_options_t {
+    /// Locally (process local) unique ID that represents this init/shutdown cycle.
+    pub instance_id: u64,
+    /// Implementation identifier, used to ensure two different implementations are not being mixed.
+    pub implementation_identifier: *const c_char,
+    #[cfg(feature = "galactic+")]
+    /// ROS domain id
+    pub domain_id: usize,
+    /// Security options
+    pub security_options: rmw_security_options_t,
+    /// Enable localhost only
+    pub localhost_only: rmw_localhost_only_t,
+    /// Enclave, name used to find security artifacts in a sros2 keystore.
+    pub enclave: *mut c_char,
+    /// Allocator used during internal allocation of init options, if needed.
+    pub allocator: rcutils_allocator_t,
+    /// Implementation defined init options.
+    impl_: *mut rmw_init_options_impl_t,
+}
@@ -1,5 +1,8 @@
 //! Wrapper of [rmw](https://github.com/ros2/rmw/tree/master/rmw)
 
+pub mod init_options;
+pub use init_options::*;
+
 pub mod names_and_types;
 pub use names_and_types::*;
 
@@ -12,6 +15,24 @@ pub mod ret_types {
 }
 pub use ret_types::*;
 
+// API in rmw/rmw.h
+//
+// It is declared here instead of in a submodule, whose name would repeat the one of this module.
+extern "C" {
+    /// Get the unique identifier of the publisher.
+    pub fn rmw_get_gid_for_publisher(
+        publisher: *const rmw_publisher_t,
+        gid: *mut rmw_gid_t,
+    ) -> rmw_ret_t;
+
+    /// Deserialize a ROS message of the type support.
+    pub fn rmw_deserialize(
+        serialized_message: *const rmw_serialized_message_t,
+        type_support: *const crate::rosidl_message_type_support_t,
+        ros_message: *mut std::os::raw::c_void,
+    ) -> rmw_ret_t;
+}
+
 pub mod serialized_message {
     //! API in rmw/serialized_message.h
 
@@ -30,3 +51,12 @@ pub use topic_endpoint_info_array::*;
 
 pub mod types;
 pub use types::*;
+
+pub mod validate_full_topic_name;
+pub use validate_full_topic_name::*;
+
+pub mod validate_namespace;
+pub use validate_namespace::*;
+
+pub mod validate_node_name;
+pub use validate_node_name::*;
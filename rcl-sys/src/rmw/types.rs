@@ -50,6 +50,22 @@ pub struct rmw_publisher_options_t {
     pub require_unique_network_flow_endpoints: rmw_unique_network_flow_endpoints_requirement_t,
 }
 
+/// Structure which encapsulates an rmw publisher
+#[repr(C)]
+#[derive(Debug)]
+pub struct rmw_publisher_t {
+    /// Name of the rmw implementation
+    pub implementation_identifier: *const c_char,
+    /// Type erased pointer to the publisher's data
+    pub data: *mut c_void,
+    /// The name of the ROS topic this publisher is publishing to
+    pub topic_name: *const c_char,
+    /// Publisher options.
+    pub options: rmw_publisher_options_t,
+    /// Indicate whether this publisher supports loaning messages
+    pub can_loan_messages: bool,
+}
+
 /// Options that can be used to configure the creation of a subscription in rmw.
 #[repr(C)]
 #[derive(Debug)]
@@ -217,6 +233,12 @@ pub struct rmw_gid_t {
 pub struct rmw_message_info_t {
     pub source_timestamp: rmw_time_point_value_t,
     pub received_timestamp: rmw_time_point_value_t,
+    /// Sequence number of the message given by the publisher
+    #[cfg(feature = "rolling")]
+    pub publication_sequence_number: u64,
+    /// Sequence number of the message given by the subscription
+    #[cfg(feature = "rolling")]
+    pub reception_sequence_number: u64,
     pub publisher_gid: rmw_gid_t,
     /// Whether this message is from intra_process communication or not
     pub from_intra_process: bool,
@@ -227,6 +249,10 @@ extern "C" {
     pub fn rmw_get_zero_initialized_message_info() -> rmw_message_info_t;
 }
 
+/// Sequence number which indicates that the middleware does not support sequence numbers
+#[cfg(feature = "rolling")]
+pub const RMW_MESSAGE_INFO_SEQUENCE_NUMBER_UNSUPPORTED: u64 = 0;
+
 /// Default size of the rmw queue when history is set to RMW_QOS_POLICY_HISTORY_KEEP_LAST,
 /// 0 indicates it is currently not set
 pub const RMW_QOS_POLICY_DEPTH_SYSTEM_DEFAULT: usize = 0;
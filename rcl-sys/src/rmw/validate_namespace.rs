@@ -0,0 +1,26 @@
+//! API in rmw/validate_namespace.h
+
+use std::os::raw::{c_char, c_int};
+
+use crate::rmw::rmw_ret_t;
+
+pub const RMW_NAMESPACE_VALID: c_int = 0;
+pub const RMW_NAMESPACE_INVALID_IS_EMPTY_STRING: c_int = 1;
+pub const RMW_NAMESPACE_INVALID_NOT_ABSOLUTE: c_int = 2;
+pub const RMW_NAMESPACE_INVALID_ENDS_WITH_FORWARD_SLASH: c_int = 3;
+pub const RMW_NAMESPACE_INVALID_CONTAINS_UNALLOWED_CHARACTERS: c_int = 4;
+pub const RMW_NAMESPACE_INVALID_CONTAINS_REPEATED_FORWARD_SLASH: c_int = 5;
+pub const RMW_NAMESPACE_INVALID_NAME_TOKEN_STARTS_WITH_NUMBER: c_int = 6;
+pub const RMW_NAMESPACE_INVALID_TOO_LONG: c_int = 7;
+
+extern "C" {
+    /// Determine if a given namespace is valid.
+    pub fn rmw_validate_namespace(
+        namespace_: *const c_char,
+        validation_result: *mut c_int,
+        invalid_index: *mut usize,
+    ) -> rmw_ret_t;
+
+    /// Return a validation result description, or NULL if unknown or RMW_NAMESPACE_VALID.
+    pub fn rmw_namespace_validation_result_string(validation_result: c_int) -> *const c_char;
+}
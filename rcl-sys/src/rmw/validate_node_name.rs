@@ -0,0 +1,23 @@
+//! API in rmw/validate_node_name.h
+
+use std::os::raw::{c_char, c_int};
+
+use crate::rmw::rmw_ret_t;
+
+pub const RMW_NODE_NAME_VALID: c_int = 0;
+pub const RMW_NODE_NAME_INVALID_IS_EMPTY_STRING: c_int = 1;
+pub const RMW_NODE_NAME_INVALID_CONTAINS_UNALLOWED_CHARACTERS: c_int = 2;
+pub const RMW_NODE_NAME_INVALID_STARTS_WITH_NUMBER: c_int = 3;
+pub const RMW_NODE_NAME_INVALID_TOO_LONG: c_int = 4;
+
+extern "C" {
+    /// Determine if a node name is valid.
+    pub fn rmw_validate_node_name(
+        node_name: *const c_char,
+        validation_result: *mut c_int,
+        invalid_index: *mut usize,
+    ) -> rmw_ret_t;
+
+    /// Return a validation result description, or NULL if unknown or RMW_NODE_NAME_VALID.
+    pub fn rmw_node_name_validation_result_string(validation_result: c_int) -> *const c_char;
+}
@@ -3,7 +3,7 @@ use std::{convert::TryInto, os::raw::c_void};
 use array_init::array_init;
 use widestring::U16String;
 
-pub trait MessageT: Default + Send + Sync {
+pub trait MessageT: Default + Clone + Send + Sync {
     type Raw: FFIToRust<Target = Self> + Default + Drop + Send + Sync;
     type RawRef: FFIFromRust<From = Self>;
 
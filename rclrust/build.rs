@@ -1,5 +1,4 @@
-#[allow(unused_imports)]
-use std::env;
+use std::{env, process::Command};
 
 #[cfg(not(any(feature = "foxy", feature = "galactic", feature = "rolling")))]
 compile_error!("Any distribution feature should be specified");
@@ -11,4 +10,25 @@ fn main() {
     assert_eq!(env::var("ROS_DISTRO"), Ok("galactic".to_string()));
     #[cfg(feature = "rolling")]
     assert_eq!(env::var("ROS_DISTRO"), Ok("rolling".to_string()));
+
+    // Builds with the same ID have the same layouts of types, which component libraries must
+    // share with the container.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    let mut features = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .collect::<Vec<_>>();
+    features.sort();
+    println!(
+        "cargo:rustc-env=RCLRUST_BUILD_ID=rclrust {} ({}) {} [{}]",
+        env::var("CARGO_PKG_VERSION").unwrap(),
+        rustc_version.trim(),
+        env::var("TARGET").unwrap(),
+        features.join(",")
+    );
 }
@@ -0,0 +1,51 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use rclrust::{
+    component::{Component, ComponentContainer, ComponentOptions, ComponentRegistry},
+    node::Node,
+    qos::QoSProfile,
+    timer::Timer,
+};
+use rclrust_msg::std_msgs::msg::String as String_;
+
+struct Talker {
+    node: Node,
+    _timer: Arc<Timer>,
+}
+
+impl Component for Talker {
+    fn new(options: ComponentOptions) -> Result<Self> {
+        let mut node = options.create_node("talker")?;
+        let publisher = node.create_publisher::<String_>("chatter", &QoSProfile::default())?;
+        let timer = node.create_wall_timer(Duration::from_millis(500), move || {
+            let _ = publisher.publish(&String_ {
+                data: "hello".into(),
+            });
+        })?;
+
+        Ok(Self {
+            node,
+            _timer: timer,
+        })
+    }
+
+    fn node(&self) -> &Node {
+        &self.node
+    }
+}
+
+// Load the talker with `ros2 component load /ComponentManager examples examples::Talker`.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let ctx = rclrust::init()?;
+
+    let mut registry = ComponentRegistry::new();
+    registry.register::<Talker>("examples::Talker");
+
+    let node = ctx.create_node("ComponentManager")?;
+    let mut container = ComponentContainer::new(node, registry)?;
+    container.wait();
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use rclrust::{
+    rclrust_info_expression, rclrust_info_once, rclrust_info_skipfirst, rclrust_warn_throttle,
+    Clock, Duration, Logger,
+};
+
+fn main() -> Result<()> {
+    let _ctx = rclrust::init()?;
+
+    let logger = Logger::new("conditional log example");
+    let clock = Clock::steady()?;
+
+    for i in 0..10 {
+        rclrust_info_once!(logger, "info log only at the first time: {}", i);
+        rclrust_info_skipfirst!(logger, "info log except the first time: {}", i);
+        rclrust_info_expression!(logger, i % 3 == 0, "info log every 3 times: {}", i);
+        rclrust_warn_throttle!(
+            logger,
+            clock,
+            Duration::new(1, 0),
+            "warn log once per sec: {}",
+            i
+        );
+        std::thread::sleep(std::time::Duration::from_millis(300));
+    }
+
+    Ok(())
+}
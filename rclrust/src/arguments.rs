@@ -0,0 +1,523 @@
+//! Command line arguments, including ROS specific ones given after `--ros-args`
+
+use std::{
+    ffi::CString,
+    mem,
+    os::raw::{c_char, c_int},
+    ptr, slice,
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    error::ToRclRustResult,
+    internal::ffi::{deallocate, take_c_string},
+    log::Logger,
+    rclrust_error,
+};
+#[cfg(feature = "galactic+")]
+use crate::{internal::ffi::SizedFromCChar, log::LogSeverity};
+
+#[derive(Debug)]
+pub(crate) struct RclArguments(rcl_sys::rcl_arguments_t);
+
+unsafe impl Send for RclArguments {}
+
+impl RclArguments {
+    fn parse(args: &[String]) -> Result<Self> {
+        let args = args
+            .iter()
+            .map(|arg| CString::new(arg.as_str()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let c_args: Vec<*const _> = args.iter().map(|s| s.as_ptr()).collect();
+        let argv = if args.is_empty() {
+            ptr::null()
+        } else {
+            c_args.as_ptr()
+        };
+
+        let mut arguments = unsafe { rcl_sys::rcl_get_zero_initialized_arguments() };
+        unsafe {
+            rcl_sys::rcl_parse_arguments(
+                c_args.len() as c_int,
+                argv,
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut arguments,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_parse_arguments in RclArguments::parse")?;
+        }
+
+        Ok(Self(arguments))
+    }
+
+    pub(crate) fn copy_from(arguments: &rcl_sys::rcl_arguments_t) -> Result<Self> {
+        let mut copied = unsafe { rcl_sys::rcl_get_zero_initialized_arguments() };
+        unsafe {
+            rcl_sys::rcl_arguments_copy(arguments, &mut copied)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_arguments_copy in RclArguments::copy_from")?;
+        }
+
+        Ok(Self(copied))
+    }
+
+    /// Take ownership of an arguments handle, which is finalized on drop.
+    ///
+    /// # Safety
+    ///
+    /// `arguments` must be initialized and must not be finalized elsewhere.
+    pub(crate) const unsafe fn from_raw(arguments: rcl_sys::rcl_arguments_t) -> Self {
+        Self(arguments)
+    }
+
+    /// Release ownership of the handle without finalizing it.
+    pub(crate) fn into_raw(self) -> rcl_sys::rcl_arguments_t {
+        let this = mem::ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.0) }
+    }
+
+    #[inline]
+    pub const fn raw(&self) -> &rcl_sys::rcl_arguments_t {
+        &self.0
+    }
+}
+
+impl Drop for RclArguments {
+    fn drop(&mut self) {
+        if let Err(e) = unsafe { rcl_sys::rcl_arguments_fini(&mut self.0).to_result() } {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to clean up rcl arguments handle: {}",
+                e
+            )
+        }
+    }
+}
+
+struct Substitutions(rcl_sys::rcutils_string_map_t);
+
+impl Substitutions {
+    fn new() -> Result<Self> {
+        let mut map = Self(unsafe { rcl_sys::rcutils_get_zero_initialized_string_map() });
+        unsafe {
+            rcl_sys::rcutils_string_map_init(
+                &mut map.0,
+                0,
+                rcl_sys::rcutils_get_default_allocator(),
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcutils_string_map_init in Substitutions::new")?;
+            rcl_sys::rcl_get_default_topic_name_substitutions(&mut map.0)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_get_default_topic_name_substitutions in Substitutions::new"
+                })?;
+        }
+        Ok(map)
+    }
+}
+
+impl Drop for Substitutions {
+    fn drop(&mut self) {
+        if let Err(e) = unsafe { rcl_sys::rcutils_string_map_fini(&mut self.0).to_result() } {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to clean up topic name substitutions: {}",
+                e
+            )
+        }
+    }
+}
+
+/// Log levels given by `--log-level` arguments
+#[cfg(feature = "galactic+")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogLevels {
+    /// Level of the default logger, or `None` if it is not given
+    pub default_level: Option<LogSeverity>,
+    /// Levels of the named loggers in the given order
+    pub logger_levels: Vec<(String, LogSeverity)>,
+}
+
+/// Parsed command line arguments
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::Arguments;
+///
+/// let args = ["my_node", "--verbose", "--ros-args", "-r", "chatter:=talk"];
+/// let arguments = Arguments::parse(args.iter().map(|s| s.to_string()).collect()).unwrap();
+/// assert_eq!(arguments.non_ros_args().unwrap(), ["my_node", "--verbose"]);
+/// ```
+#[derive(Debug)]
+pub struct Arguments {
+    handle: RclArguments,
+    args: Vec<String>,
+}
+
+impl Arguments {
+    /// Parse command line arguments, where the first one is the program name.
+    pub fn parse(args: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            handle: RclArguments::parse(&args)?,
+            args,
+        })
+    }
+
+    pub(crate) fn from_raw(
+        arguments: &rcl_sys::rcl_arguments_t,
+        args: Vec<String>,
+    ) -> Result<Self> {
+        Ok(Self {
+            handle: RclArguments::copy_from(arguments)?,
+            args,
+        })
+    }
+
+    pub(crate) const fn raw(&self) -> &rcl_sys::rcl_arguments_t {
+        self.handle.raw()
+    }
+
+    /// Get all of the arguments, as given on parsing.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Get the arguments which are not ROS specific, including the program name.
+    pub fn non_ros_args(&self) -> Result<Vec<String>> {
+        let count = unsafe { rcl_sys::rcl_arguments_get_count_unparsed(self.raw()) };
+        self.args_at(count, rcl_sys::rcl_arguments_get_unparsed)
+            .with_context(|| "rcl_sys::rcl_arguments_get_unparsed in Arguments::non_ros_args")
+    }
+
+    /// Get the ROS specific arguments which are unknown to rcl.
+    pub fn unknown_ros_args(&self) -> Result<Vec<String>> {
+        let count = unsafe { rcl_sys::rcl_arguments_get_count_unparsed_ros(self.raw()) };
+        self.args_at(count, rcl_sys::rcl_arguments_get_unparsed_ros)
+            .with_context(|| {
+                "rcl_sys::rcl_arguments_get_unparsed_ros in Arguments::unknown_ros_args"
+            })
+    }
+
+    fn args_at(
+        &self,
+        count: c_int,
+        get_indices: unsafe extern "C" fn(
+            *const rcl_sys::rcl_arguments_t,
+            rcl_sys::rcl_allocator_t,
+            *mut *mut c_int,
+        ) -> rcl_sys::rcl_ret_t,
+    ) -> Result<Vec<String>> {
+        if count <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut indices = ptr::null_mut();
+        unsafe {
+            get_indices(
+                self.raw(),
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut indices,
+            )
+            .to_result()?;
+        }
+        let args = unsafe { slice::from_raw_parts(indices, count as usize) }
+            .iter()
+            .map(|&index| self.args[index as usize].clone())
+            .collect();
+        unsafe { deallocate(indices) };
+
+        Ok(args)
+    }
+
+    /// Get the parameter files given by `--params-file` arguments.
+    pub fn param_files(&self) -> Result<Vec<String>> {
+        let count = unsafe { rcl_sys::rcl_arguments_get_param_files_count(self.raw()) };
+        if count <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut files = ptr::null_mut();
+        unsafe {
+            rcl_sys::rcl_arguments_get_param_files(
+                self.raw(),
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut files,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_arguments_get_param_files in Arguments::param_files")?;
+        }
+        let param_files = unsafe { slice::from_raw_parts(files, count as usize) }
+            .iter()
+            .filter_map(|&file| unsafe { take_c_string(file) })
+            .collect();
+        unsafe { deallocate(files) };
+
+        Ok(param_files)
+    }
+
+    /// Get the log levels given by `--log-level` arguments.
+    #[cfg(feature = "galactic+")]
+    pub fn log_levels(&self) -> Result<LogLevels> {
+        let mut log_levels = unsafe { rcl_sys::rcl_get_zero_initialized_log_levels() };
+        unsafe {
+            rcl_sys::rcl_arguments_get_log_levels(self.raw(), &mut log_levels)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_arguments_get_log_levels in Arguments::log_levels"
+                })?;
+        }
+
+        let settings = if log_levels.logger_settings.is_null() {
+            &[]
+        } else {
+            unsafe {
+                slice::from_raw_parts(log_levels.logger_settings, log_levels.num_logger_settings)
+            }
+        };
+        let result = LogLevels {
+            default_level: LogSeverity::try_from_int(log_levels.default_logger_level)
+                .filter(|&level| level != LogSeverity::Unset),
+            logger_levels: settings
+                .iter()
+                .filter_map(|setting| {
+                    let name = unsafe { String::from_c_char(setting.name) }?;
+                    Some((name, LogSeverity::try_from_int(setting.level)?))
+                })
+                .collect(),
+        };
+
+        unsafe {
+            rcl_sys::rcl_log_levels_fini(&mut log_levels)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_log_levels_fini in Arguments::log_levels")?;
+        }
+
+        Ok(result)
+    }
+
+    /// Remap a fully qualified topic name with the remap rules of the arguments, returning
+    /// `None` if no rule matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Arguments;
+    ///
+    /// let args = ["my_node", "--ros-args", "-r", "chatter:=talk"];
+    /// let arguments = Arguments::parse(args.iter().map(|s| s.to_string()).collect()).unwrap();
+    /// assert_eq!(
+    ///     arguments
+    ///         .remap_topic_name("/chatter", "my_node", "/")
+    ///         .unwrap(),
+    ///     Some("/talk".to_string())
+    /// );
+    /// ```
+    pub fn remap_topic_name(
+        &self,
+        topic_name: &str,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> Result<Option<String>> {
+        self.remap_name(
+            topic_name,
+            node_name,
+            node_namespace,
+            rcl_sys::rcl_remap_topic_name,
+        )
+        .with_context(|| "rcl_sys::rcl_remap_topic_name in Arguments::remap_topic_name")
+    }
+
+    /// Remap a fully qualified service name with the remap rules of the arguments, returning
+    /// `None` if no rule matches.
+    pub fn remap_service_name(
+        &self,
+        service_name: &str,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> Result<Option<String>> {
+        self.remap_name(
+            service_name,
+            node_name,
+            node_namespace,
+            rcl_sys::rcl_remap_service_name,
+        )
+        .with_context(|| "rcl_sys::rcl_remap_service_name in Arguments::remap_service_name")
+    }
+
+    fn remap_name(
+        &self,
+        name: &str,
+        node_name: &str,
+        node_namespace: &str,
+        remap: unsafe extern "C" fn(
+            *const rcl_sys::rcl_arguments_t,
+            *const rcl_sys::rcl_arguments_t,
+            *const c_char,
+            *const c_char,
+            *const c_char,
+            *const rcl_sys::rcutils_string_map_t,
+            rcl_sys::rcl_allocator_t,
+            *mut *mut c_char,
+        ) -> rcl_sys::rcl_ret_t,
+    ) -> Result<Option<String>> {
+        let name = CString::new(name)?;
+        let node_name = CString::new(node_name)?;
+        let node_namespace = CString::new(node_namespace)?;
+        let substitutions = Substitutions::new()?;
+
+        let mut output = ptr::null_mut();
+        unsafe {
+            remap(
+                self.raw(),
+                ptr::null(),
+                name.as_ptr(),
+                node_name.as_ptr(),
+                node_namespace.as_ptr(),
+                &substitutions.0,
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut output,
+            )
+            .to_result()?;
+            Ok(take_c_string(output))
+        }
+    }
+
+    /// Remap a node name with the remap rules of the arguments, returning `None` if no rule
+    /// matches.
+    pub fn remap_node_name(&self, node_name: &str) -> Result<Option<String>> {
+        let node_name = CString::new(node_name)?;
+
+        let mut output = ptr::null_mut();
+        unsafe {
+            rcl_sys::rcl_remap_node_name(
+                self.raw(),
+                ptr::null(),
+                node_name.as_ptr(),
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut output,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_remap_node_name in Arguments::remap_node_name")?;
+            Ok(take_c_string(output))
+        }
+    }
+
+    /// Remap the namespace of a node with the remap rules of the arguments, returning `None` if
+    /// no rule matches.
+    pub fn remap_node_namespace(&self, node_name: &str) -> Result<Option<String>> {
+        let node_name = CString::new(node_name)?;
+
+        let mut output = ptr::null_mut();
+        unsafe {
+            rcl_sys::rcl_remap_node_namespace(
+                self.raw(),
+                ptr::null(),
+                node_name.as_ptr(),
+                rcl_sys::rcutils_get_default_allocator(),
+                &mut output,
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_remap_node_namespace in Arguments::remap_node_namespace"
+            })?;
+            Ok(take_c_string(output))
+        }
+    }
+}
+
+/// Remove ROS specific arguments, so that the rest can be parsed by another parser.
+///
+/// The program name is kept as the first argument.
+///
+/// # Examples
+///
+/// ```
+/// let args = ["my_node", "--ros-args", "-p", "param:=1", "--", "--verbose"];
+/// let args = rclrust::remove_ros_args(args.iter().map(|s| s.to_string()).collect()).unwrap();
+/// assert_eq!(args, ["my_node", "--verbose"]);
+/// ```
+pub fn remove_ros_args(args: Vec<String>) -> Result<Vec<String>> {
+    Arguments::parse(args)?.non_ros_args()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_arguments() -> Result<()> {
+        // Removed when dropped at the end of the test.
+        let mut file = tempfile::Builder::new().suffix(".yaml").tempfile()?;
+        writeln!(file, "test_node:\n  ros__parameters:\n    param: 1")?;
+        let param_file = file.path().to_str().unwrap();
+
+        let arguments = Arguments::parse(to_args(&[
+            "prog",
+            "--foo",
+            "--ros-args",
+            "-r",
+            "__node:=renamed",
+            "--params-file",
+            param_file,
+            "--unknown",
+            "--",
+            "bar",
+        ]))?;
+        assert_eq!(arguments.non_ros_args()?, ["prog", "--foo", "bar"]);
+        assert_eq!(arguments.unknown_ros_args()?, ["--unknown"]);
+        assert_eq!(arguments.param_files()?, [param_file]);
+        assert_eq!(
+            arguments.remap_node_name("test_node")?,
+            Some("renamed".to_string())
+        );
+        assert_eq!(arguments.remap_node_namespace("test_node")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_empty_arguments() -> Result<()> {
+        let arguments = Arguments::parse(Vec::new())?;
+        assert!(arguments.non_ros_args()?.is_empty());
+        assert!(arguments.param_files()?.is_empty());
+        assert_eq!(arguments.remap_topic_name("/chatter", "node", "/")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid_arguments() {
+        assert!(Arguments::parse(to_args(&["prog", "--ros-args", "-r"])).is_err());
+    }
+
+    #[cfg(feature = "galactic+")]
+    #[test]
+    fn parse_log_levels() -> Result<()> {
+        let arguments = Arguments::parse(to_args(&[
+            "prog",
+            "--ros-args",
+            "--log-level",
+            "warn",
+            "--log-level",
+            "my_logger:=debug",
+        ]))?;
+        assert_eq!(
+            arguments.log_levels()?,
+            LogLevels {
+                default_level: Some(LogSeverity::Warn),
+                logger_levels: vec![("my_logger".to_string(), LogSeverity::Debug)],
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Options of the queue which holds messages until their callback is called
+
+/// Behavior when the callback queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueOverflowPolicy {
+    /// Drop the oldest message in the queue to make room for the new one.
+    DropOldest,
+    /// Drop the new message.
+    DropNewest,
+    /// Block the executor until the callback consumes a message.
+    Block,
+    /// Return an error, which stops the executor of the node.
+    Error,
+}
+
+/// Options of the queue between the executor and a callback
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::callback_queue::{CallbackQueueOptions, QueueOverflowPolicy};
+///
+/// let options = CallbackQueueOptions::new()
+///     .depth(100)
+///     .overflow_policy(QueueOverflowPolicy::DropOldest);
+/// assert_eq!(options.depth, 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallbackQueueOptions {
+    /// The number of messages which can be queued. `0` is treated as `1`.
+    pub depth: usize,
+    /// Behavior when the queue is full
+    pub overflow_policy: QueueOverflowPolicy,
+}
+
+impl Default for CallbackQueueOptions {
+    fn default() -> Self {
+        Self {
+            depth: 10,
+            overflow_policy: QueueOverflowPolicy::Error,
+        }
+    }
+}
+
+impl CallbackQueueOptions {
+    /// Construct a new `CallbackQueueOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the queue depth.
+    pub const fn depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+
+    /// Set the behavior when the queue is full.
+    pub const fn overflow_policy(self, overflow_policy: QueueOverflowPolicy) -> Self {
+        Self {
+            overflow_policy,
+            ..self
+        }
+    }
+}
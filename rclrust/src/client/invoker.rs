@@ -1,11 +1,14 @@
 use std::{fmt, sync::Arc};
 
 use anyhow::Result;
-use futures::channel::mpsc;
 use rclrust_msg::_core::ServiceT;
 
 use super::{ChannelMessage, Client, RclClient};
-use crate::{error::RclRustError, internal::worker::WorkerMessage, rclrust_debug, Logger};
+use crate::{
+    error::RclRustError,
+    internal::worker::{WorkerSendError, WorkerSender},
+    rclrust_debug, Logger,
+};
 
 pub trait ClientInvokerBase: fmt::Debug {
     fn handle(&self) -> &RclClient;
@@ -17,7 +20,7 @@ where
     Srv: ServiceT,
 {
     handle: Arc<RclClient>,
-    tx: Option<mpsc::Sender<WorkerMessage<ChannelMessage<Srv>>>>,
+    tx: Option<WorkerSender<ChannelMessage<Srv>>>,
 }
 
 impl<Srv> ClientInvoker<Srv>
@@ -72,10 +75,10 @@ where
                 }
             };
 
-            match tx.try_send(WorkerMessage::Message(res)) {
+            match tx.send(res) {
                 Ok(_) => (),
-                Err(e) if e.is_disconnected() => self.stop(),
-                Err(_) => {
+                Err(WorkerSendError::Disconnected) => self.stop(),
+                Err(WorkerSendError::Full) => {
                     return Err(RclRustError::MessageQueueIsFull {
                         type_: "Client",
                         name: self.handle.service_name().expect("Client should be valid"),
@@ -8,11 +8,12 @@ use std::{
 };
 
 use anyhow::Result;
-use futures::channel::{mpsc, oneshot};
+use futures::channel::oneshot;
 use rclrust_msg::_core::{FFIToRust, ServiceResponseRaw, ServiceT};
 
 use crate::{
-    internal::worker::{ReceiveWorker, WorkerMessage},
+    callback_queue::CallbackQueueOptions,
+    internal::worker::{ReceiveWorker, WorkerSender},
     node::Node,
     qos::QoSProfile,
 };
@@ -25,6 +26,33 @@ pub use invoker::{ClientInvoker, ClientInvokerBase};
 
 type ChannelMessage<Srv> = (rcl_sys::rmw_request_id_t, ServiceResponseRaw<Srv>);
 
+/// Options for creating a client
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// Queue between the executor and the response handling
+    pub callback_queue: CallbackQueueOptions,
+}
+
+impl ClientOptions {
+    /// Construct a new `ClientOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the options of the queue between the executor and the response handling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{callback_queue::CallbackQueueOptions, ClientOptions};
+    /// #
+    /// let options = ClientOptions::new().callback_queue(CallbackQueueOptions::new().depth(100));
+    /// ```
+    pub const fn callback_queue(self, callback_queue: CallbackQueueOptions) -> Self {
+        Self { callback_queue }
+    }
+}
+
 /// Service client
 pub struct Client<Srv>
 where
@@ -39,7 +67,13 @@ impl<Srv> Client<Srv>
 where
     Srv: ServiceT,
 {
-    pub(crate) fn new(node: &Node, service_name: &str, qos: &QoSProfile) -> Result<Self> {
+    pub(crate) fn new(
+        node: &Node,
+        service_name: &str,
+        qos: &QoSProfile,
+        queue_options: &CallbackQueueOptions,
+    ) -> Result<Self> {
+        node.resolve_name(service_name, true, false)?;
         let handle = Arc::new(RclClient::new::<Srv>(
             node.clone_handle(),
             service_name,
@@ -68,7 +102,7 @@ where
 
         Ok(Self {
             handle,
-            worker: ReceiveWorker::new(callback),
+            worker: ReceiveWorker::new(callback, queue_options),
             pendings,
         })
     }
@@ -198,7 +232,7 @@ where
         Arc::clone(&self.handle)
     }
 
-    pub(crate) fn clone_tx(&self) -> mpsc::Sender<WorkerMessage<ChannelMessage<Srv>>> {
+    pub(crate) fn clone_tx(&self) -> WorkerSender<ChannelMessage<Srv>> {
         self.worker.clone_tx()
     }
 }
@@ -252,6 +286,97 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn service_req_res_with_options() -> Result<()> {
+        use rclrust_msg::example_interfaces::srv::{
+            AddTwoInts, AddTwoInts_Request, AddTwoInts_Response,
+        };
+
+        use crate::{
+            callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+            ServiceOptions,
+        };
+
+        let ctx = crate::init()?;
+        let mut service_node = ctx.create_node(&random_name())?;
+
+        let service_name = random_name();
+        let callback_queue = CallbackQueueOptions::new()
+            .depth(1)
+            .overflow_policy(QueueOverflowPolicy::Block);
+
+        let _srv = service_node.create_service_with_options::<AddTwoInts, _>(
+            &service_name,
+            |req| AddTwoInts_Response { sum: req.a + req.b },
+            &QoSProfile::default(),
+            &ServiceOptions::new().callback_queue(callback_queue),
+        )?;
+
+        let mut client_node = ctx.create_node(&random_name())?;
+        let mut client = client_node.create_client_with_options::<AddTwoInts>(
+            &service_name,
+            &QoSProfile::default(),
+            &ClientOptions::new().callback_queue(callback_queue),
+        )?;
+        client.wait_service()?;
+        assert_eq!(
+            client
+                .send_request(&AddTwoInts_Request { a: 15, b: 27 })
+                .await?
+                .sum,
+            42
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn service_req_res_with_info() -> Result<()> {
+        use std::sync::{
+            atomic::{AtomicI64, Ordering},
+            Arc,
+        };
+
+        use rclrust_msg::example_interfaces::srv::{
+            AddTwoInts, AddTwoInts_Request, AddTwoInts_Response,
+        };
+
+        let ctx = crate::init()?;
+        let mut service_node = ctx.create_node(&random_name())?;
+
+        let service_name = random_name();
+
+        let sequence_number = Arc::new(AtomicI64::new(0));
+        let _srv = {
+            let sequence_number = Arc::clone(&sequence_number);
+            service_node.create_service_with_info::<AddTwoInts, _>(
+                &service_name,
+                move |req, info| {
+                    assert!(info.received_timestamp.nanosecs >= info.source_timestamp.nanosecs);
+                    sequence_number.store(info.request_id.sequence_number, Ordering::Relaxed);
+                    AddTwoInts_Response { sum: req.a + req.b }
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let mut client_node = ctx.create_node(&random_name())?;
+        let mut client =
+            client_node.create_client::<AddTwoInts>(&service_name, &QoSProfile::default())?;
+        client.wait_service()?;
+        assert_eq!(
+            client
+                .send_request(&AddTwoInts_Request { a: 15, b: 26 })
+                .await?
+                .sum,
+            41
+        );
+        // The sequence number of the first request of a client
+        assert_eq!(sequence_number.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn client_service_name() -> Result<()> {
         let ctx = crate::init()?;
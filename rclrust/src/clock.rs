@@ -1,22 +1,201 @@
-use std::mem::MaybeUninit;
+use std::{
+    cell::UnsafeCell,
+    convert::{TryFrom, TryInto},
+    fmt,
+    mem::{self, MaybeUninit},
+    os::raw::c_void,
+    sync::{Arc, Condvar, Mutex, Weak},
+    task::{Poll, Waker},
+    time::Duration,
+};
 
 use anyhow::{ensure, Context, Result};
+use futures::future;
+use once_cell::sync::OnceCell;
+use parking_lot::ReentrantMutex;
 pub use rcl_sys::RclClockType as ClockType;
 
-use crate::{error::ToRclRustResult, log::Logger, rclrust_error, time::Time};
+use crate::{
+    context, duration, error::ToRclRustResult, internal::async_api, log::Logger, rclrust_error,
+    time::Time,
+};
 
-#[derive(Debug)]
-pub struct Clock(rcl_sys::rcl_clock_t);
+/// Kind of change of the time source in a time jump
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClockChange {
+    /// The source before and after the jump is ROS time.
+    RosTimeNoChange,
+    /// The source switched to ROS time from the system time.
+    RosTimeActivated,
+    /// The source switched to the system time from ROS time.
+    RosTimeDeactivated,
+    /// The source before and after the jump is the system time.
+    SystemTimeNoChange,
+}
 
-unsafe impl Send for Clock {}
+impl From<rcl_sys::rcl_clock_change_t> for ClockChange {
+    fn from(clock_change: rcl_sys::rcl_clock_change_t) -> Self {
+        use rcl_sys::rcl_clock_change_t::*;
 
-impl Clock {
-    pub(crate) fn new(clock_type: ClockType) -> Result<Self> {
-        ensure!(
-            clock_type != ClockType::Uninitialized,
-            "`ClockType::Uninitialized` is invalid type."
-        );
+        match clock_change {
+            RCL_ROS_TIME_NO_CHANGE => Self::RosTimeNoChange,
+            RCL_ROS_TIME_ACTIVATED => Self::RosTimeActivated,
+            RCL_ROS_TIME_DEACTIVATED => Self::RosTimeDeactivated,
+            RCL_SYSTEM_TIME_NO_CHANGE => Self::SystemTimeNoChange,
+        }
+    }
+}
+
+/// Jump in time of a clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeJump {
+    /// Whether or not the source of time changed
+    pub clock_change: ClockChange,
+    /// The new time minus the last time before the jump
+    pub delta: duration::Duration,
+}
+
+impl From<&rcl_sys::rcl_time_jump_t> for TimeJump {
+    fn from(time_jump: &rcl_sys::rcl_time_jump_t) -> Self {
+        Self {
+            clock_change: time_jump.clock_change.into(),
+            delta: (&time_jump.delta).into(),
+        }
+    }
+}
+
+/// Conditions under which jump callbacks are called
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use rclrust::clock::JumpThreshold;
+///
+/// let threshold = JumpThreshold::new()
+///     .on_clock_change(true)
+///     .min_backward(Duration::from_nanos(1));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JumpThreshold {
+    /// Call the callbacks when the source of time changes.
+    pub on_clock_change: bool,
+    /// Minimum jump forwards to call the callbacks. Zero disables it.
+    pub min_forward: Duration,
+    /// Minimum jump backwards to call the callbacks. Zero disables it.
+    pub min_backward: Duration,
+}
+
+impl JumpThreshold {
+    /// Construct a new `JumpThreshold` which never calls the callbacks.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set whether the callbacks are called when the source of time changes.
+    pub const fn on_clock_change(self, on_clock_change: bool) -> Self {
+        Self {
+            on_clock_change,
+            ..self
+        }
+    }
+
+    /// Set the minimum jump forwards to call the callbacks.
+    pub const fn min_forward(self, min_forward: Duration) -> Self {
+        Self {
+            min_forward,
+            ..self
+        }
+    }
+
+    /// Set the minimum jump backwards to call the callbacks.
+    pub const fn min_backward(self, min_backward: Duration) -> Self {
+        Self {
+            min_backward,
+            ..self
+        }
+    }
+
+    fn to_rcl(self) -> Result<rcl_sys::rcl_jump_threshold_t> {
+        let min_forward: i64 = self.min_forward.as_nanos().try_into()?;
+        let min_backward: i64 = self.min_backward.as_nanos().try_into()?;
+        Ok(rcl_sys::rcl_jump_threshold_t {
+            on_clock_change: self.on_clock_change,
+            min_forward: rcl_sys::rcl_duration_t {
+                nanoseconds: min_forward,
+            },
+            min_backward: rcl_sys::rcl_duration_t {
+                nanoseconds: -min_backward,
+            },
+        })
+    }
+}
+
+type PreJumpCallback = Box<dyn Fn() + Send + Sync>;
+type PostJumpCallback = Box<dyn Fn(&TimeJump) + Send + Sync>;
+
+struct JumpCallback {
+    pre: PreJumpCallback,
+    post: PostJumpCallback,
+}
+
+unsafe extern "C" fn invoke_jump_callback(
+    time_jump: *const rcl_sys::rcl_time_jump_t,
+    before_jump: bool,
+    user_data: *mut c_void,
+) {
+    let callback = &*(user_data as *const JumpCallback);
+    if before_jump {
+        (callback.pre)();
+    } else {
+        (callback.post)(&(&*time_jump).into());
+    }
+}
 
+/// Handle of callbacks registered by [`Clock::on_jump`]
+///
+/// The callbacks are unregistered from the clock when this is dropped, so it must not be
+/// dropped inside a jump callback of the same clock.
+pub struct JumpHandler {
+    clock: Arc<RclClock>,
+    callback: Box<JumpCallback>,
+}
+
+impl fmt::Debug for JumpHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JumpHandler {{ clock: {:?} }}", self.clock)
+    }
+}
+
+impl Drop for JumpHandler {
+    fn drop(&mut self) {
+        let user_data = &*self.callback as *const JumpCallback as *mut c_void;
+        let result = self.clock.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_clock_remove_jump_callback(raw, Some(invoke_jump_callback), user_data)
+                .to_result()
+        });
+        if let Err(e) = result {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to remove jump callback: {}",
+                e
+            )
+        }
+    }
+}
+
+/// rcl clock shared by a [`Clock`] and the handlers of its jump callbacks
+#[derive(Debug)]
+struct RclClock {
+    r#impl: Box<UnsafeCell<rcl_sys::rcl_clock_t>>,
+    /// Serializes all accesses to the rcl clock. It is reentrant because jump callbacks are
+    /// called with it locked and may read the time.
+    lock: ReentrantMutex<()>,
+}
+
+impl RclClock {
+    fn new(clock_type: ClockType) -> Result<Self> {
         let mut clock = MaybeUninit::uninit();
         unsafe {
             rcl_sys::rcl_clock_init(
@@ -25,13 +204,163 @@ impl Clock {
                 &mut rcl_sys::rcutils_get_default_allocator(),
             )
             .to_result()
-            .with_context(|| "rcl_sys::rcl_clock_init in Clock::new")?;
-            Ok(Self(clock.assume_init()))
+            .with_context(|| "rcl_sys::rcl_clock_init in RclClock::new")?;
+            Ok(Self {
+                r#impl: Box::new(UnsafeCell::new(clock.assume_init())),
+                lock: ReentrantMutex::new(()),
+            })
+        }
+    }
+
+    #[inline]
+    fn raw(&self) -> *mut rcl_sys::rcl_clock_t {
+        self.r#impl.get()
+    }
+
+    fn with_raw_locked<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(*mut rcl_sys::rcl_clock_t) -> R,
+    {
+        let _guard = self.lock.lock();
+        f(self.raw())
+    }
+}
+
+unsafe impl Send for RclClock {}
+unsafe impl Sync for RclClock {}
+
+impl Drop for RclClock {
+    fn drop(&mut self) {
+        if self.r#impl.get_mut().type_ != ClockType::Uninitialized {
+            if let Err(e) = unsafe { rcl_sys::rcl_clock_fini(self.r#impl.get_mut()).to_result() } {
+                rclrust_error!(
+                    Logger::new("rclrust"),
+                    "Failed to clean up rcl clock handle: {}",
+                    e
+                )
+            }
         }
     }
+}
+
+/// Wakes sleepers up when the time of a clock jumps or a context is shut down.
+#[derive(Debug, Default)]
+struct Wakeup {
+    state: Mutex<WakeupState>,
+    condvar: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct WakeupState {
+    generation: u64,
+    wakers: Vec<Waker>,
+}
+
+impl Wakeup {
+    fn notify(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.generation += 1;
+        let wakers = mem::take(&mut state.wakers);
+        drop(state);
+
+        self.condvar.notify_all();
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+
+    fn wait(&self, generation: u64, timeout: Option<Duration>) {
+        let pending = |state: &mut WakeupState| state.generation == generation;
+        let state = self.state.lock().unwrap();
+        match timeout {
+            Some(timeout) => drop(self.condvar.wait_timeout_while(state, timeout, pending)),
+            None => drop(self.condvar.wait_while(state, pending)),
+        }
+    }
+
+    async fn wait_async(&self, generation: u64, timeout: Option<Duration>) {
+        let mut registered: Option<Waker> = None;
+        {
+            let notified = future::poll_fn(|cx| {
+                let mut state = self.state.lock().unwrap();
+                if state.generation != generation {
+                    return Poll::Ready(());
+                }
+                if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    state.wakers.push(cx.waker().clone());
+                }
+                drop(state);
+                registered = Some(cx.waker().clone());
+                Poll::Pending
+            });
+            match timeout {
+                Some(timeout) => {
+                    let timeout = async_api::sleep(timeout);
+                    futures::pin_mut!(notified, timeout);
+                    future::select(notified, timeout).await;
+                }
+                None => notified.await,
+            }
+        }
+
+        // Do not keep the waker of a finished wait until the next notification.
+        if let Some(registered) = registered {
+            self.state
+                .lock()
+                .unwrap()
+                .wakers
+                .retain(|waker| !waker.will_wake(&registered));
+        }
+    }
+}
+
+enum SleepStep {
+    /// Sleeping is over. `true` if the time is reached and `false` if the context is shut down.
+    Done(bool),
+    /// Wait until the time jumps, the context is shut down or the timeout elapses if any.
+    Wait(Option<Duration>),
+}
+
+/// Clock which can be shared between threads, e.g. by wrapping it with `Arc`
+///
+/// All operations on the rcl clock are serialized, and jump callbacks are called while the
+/// clock is locked. Jump callbacks may read the time, but they must not change the time or
+/// register or drop jump handlers of the same clock.
+#[derive(Debug)]
+pub struct Clock {
+    handle: Arc<RclClock>,
+    /// Notified by the jump handler for sleepers and on shutdown of contexts.
+    wakeup: Arc<Wakeup>,
+    /// Registered on the first sleep of a ROS clock.
+    sleep_jump_handler: OnceCell<JumpHandler>,
+    /// Contexts which notify `wakeup` on shutdown
+    shutdown_notifiers: Mutex<Vec<Weak<context::ContextShutdown>>>,
+}
+
+impl Clock {
+    pub(crate) fn new(clock_type: ClockType) -> Result<Self> {
+        ensure!(
+            clock_type != ClockType::Uninitialized,
+            "`ClockType::Uninitialized` is invalid type."
+        );
+
+        Ok(Self {
+            handle: Arc::new(RclClock::new(clock_type)?),
+            wakeup: Default::default(),
+            sleep_jump_handler: OnceCell::new(),
+            shutdown_notifiers: Default::default(),
+        })
+    }
 
-    pub(crate) fn raw_mut(&mut self) -> &mut rcl_sys::rcl_clock_t {
-        &mut self.0
+    /// Call `f` with the rcl clock while no jump callback is called or modified, e.g. to
+    /// initialize or finalize an rcl timer which registers a jump callback.
+    pub(crate) fn with_raw_locked<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(*mut rcl_sys::rcl_clock_t) -> R,
+    {
+        self.handle.with_raw_locked(f)
     }
 
     /// Construct a new `Clock` with ros time
@@ -81,18 +410,18 @@ impl Clock {
     /// ```
     /// use rclrust::{Clock, ClockType};
     ///
-    /// let mut clock = Clock::ros().unwrap();
+    /// let clock = Clock::ros().unwrap();
     /// let now = clock.now().unwrap();
     /// println!("current time: {:?}", now);
     /// ```
-    pub fn now(&mut self) -> Result<Time> {
+    pub fn now(&self) -> Result<Time> {
         let mut nanosecs = 0;
 
-        unsafe {
-            rcl_sys::rcl_clock_get_now(&mut self.0, &mut nanosecs)
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_clock_get_now(raw, &mut nanosecs)
                 .to_result()
-                .with_context(|| "rcl_sys::rcl_clock_get_now in Clock::now")?;
-        }
+                .with_context(|| "rcl_sys::rcl_clock_get_now in Clock::now")
+        })?;
         Ok(Time::from_nanosecs(nanosecs, self.clock_type()))
     }
 
@@ -104,8 +433,8 @@ impl Clock {
     /// let clock = Clock::ros().unwrap();
     /// assert_eq!(clock.clock_type(), ClockType::RosTime);
     /// ```
-    pub const fn clock_type(&self) -> ClockType {
-        self.0.type_
+    pub fn clock_type(&self) -> ClockType {
+        self.with_raw_locked(|raw| unsafe { (*raw).type_ })
     }
 
     /// # Examples
@@ -113,26 +442,255 @@ impl Clock {
     /// ```
     /// use rclrust::{Clock, ClockType};
     ///
-    /// let mut clock = Clock::ros().unwrap();
+    /// let clock = Clock::ros().unwrap();
     /// assert!(clock.is_valid());
     /// ```
-    pub fn is_valid(&mut self) -> bool {
-        unsafe { rcl_sys::rcl_clock_valid(&mut self.0) }
+    pub fn is_valid(&self) -> bool {
+        self.with_raw_locked(|raw| unsafe { rcl_sys::rcl_clock_valid(raw) })
     }
-}
 
-impl Drop for Clock {
-    fn drop(&mut self) {
-        if self.clock_type() != ClockType::Uninitialized {
-            if let Err(e) = unsafe { rcl_sys::rcl_clock_fini(&mut self.0).to_result() } {
-                rclrust_error!(
-                    Logger::new("rclrust"),
-                    "Failed to clean up rcl clock handle: {}",
-                    e
-                )
+    /// Make a ROS clock return the time given by [`Clock::set_ros_time_override`] instead of
+    /// the system time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{Clock, ClockType, Time};
+    ///
+    /// let clock = Clock::ros().unwrap();
+    /// clock.enable_ros_time_override().unwrap();
+    /// clock
+    ///     .set_ros_time_override(Time::new(5, 0, ClockType::RosTime))
+    ///     .unwrap();
+    /// assert_eq!(clock.now().unwrap(), Time::new(5, 0, ClockType::RosTime));
+    /// ```
+    pub fn enable_ros_time_override(&self) -> Result<()> {
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_enable_ros_time_override(raw)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_enable_ros_time_override in Clock::enable_ros_time_override"
+                })
+        })
+    }
+
+    /// Make a ROS clock follow the system time again.
+    pub fn disable_ros_time_override(&self) -> Result<()> {
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_disable_ros_time_override(raw)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_disable_ros_time_override in Clock::disable_ros_time_override"
+                })
+        })
+    }
+
+    /// Check if the ROS time override of a ROS clock is enabled or not.
+    pub fn is_ros_time_override_enabled(&self) -> Result<bool> {
+        let mut enabled = false;
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_is_enabled_ros_time_override(raw, &mut enabled)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_is_enabled_ros_time_override in Clock::is_ros_time_override_enabled"
+                })
+        })?;
+        Ok(enabled)
+    }
+
+    /// Register callbacks called before and after a time jump which exceeds the threshold.
+    ///
+    /// The callbacks are called by the thread which changes the time, e.g. the thread which
+    /// receives `/clock`, and they are unregistered when the returned handler is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{clock::JumpThreshold, Clock};
+    ///
+    /// let clock = Clock::ros().unwrap();
+    /// let _handler = clock
+    ///     .on_jump(
+    ///         JumpThreshold::new().on_clock_change(true),
+    ///         || println!("time will jump"),
+    ///         |jump| println!("time jumped: {:?}", jump),
+    ///     )
+    ///     .unwrap();
+    /// clock.enable_ros_time_override().unwrap();
+    /// ```
+    pub fn on_jump<Pre, Post>(
+        &self,
+        threshold: JumpThreshold,
+        pre: Pre,
+        post: Post,
+    ) -> Result<JumpHandler>
+    where
+        Pre: Fn() + Send + Sync + 'static,
+        Post: Fn(&TimeJump) + Send + Sync + 'static,
+    {
+        let callback = Box::new(JumpCallback {
+            pre: Box::new(pre),
+            post: Box::new(post),
+        });
+        let threshold = threshold.to_rcl()?;
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_clock_add_jump_callback(
+                raw,
+                threshold,
+                Some(invoke_jump_callback),
+                &*callback as *const JumpCallback as *mut c_void,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_clock_add_jump_callback in Clock::on_jump")
+        })?;
+
+        Ok(JumpHandler {
+            clock: Arc::clone(&self.handle),
+            callback,
+        })
+    }
+
+    /// Block the current thread until the clock reaches `until`.
+    ///
+    /// It follows the time of the clock, including simulated time, and returns `false` early
+    /// if the context is shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{Clock, Duration};
+    ///
+    /// let ctx = rclrust::init().unwrap();
+    /// let clock = Clock::ros().unwrap();
+    /// let until = clock.now().unwrap() + Duration::from_nanosecs(1_000_000);
+    /// assert!(clock.sleep_until(until, &ctx).unwrap());
+    /// ```
+    pub fn sleep_until(&self, until: Time, context: &context::Context) -> Result<bool> {
+        self.prepare_sleep(until, context)?;
+        loop {
+            let generation = self.wakeup.generation();
+            match self.next_sleep_step(until, context)? {
+                SleepStep::Done(reached) => return Ok(reached),
+                SleepStep::Wait(timeout) => self.wakeup.wait(generation, timeout),
             }
         }
     }
+
+    /// Block the current thread for `duration` of the clock.
+    ///
+    /// See [`Clock::sleep_until`] for details.
+    pub fn sleep_for(
+        &self,
+        duration: duration::Duration,
+        context: &context::Context,
+    ) -> Result<bool> {
+        self.sleep_until(self.now()? + duration, context)
+    }
+
+    /// Asynchronous version of [`Clock::sleep_until`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{Clock, Duration};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let ctx = rclrust::init().unwrap();
+    /// let clock = Clock::steady().unwrap();
+    /// let until = clock.now().unwrap() + Duration::from_nanosecs(1_000_000);
+    /// assert!(clock.sleep_until_async(until, &ctx).await.unwrap());
+    /// # }
+    /// ```
+    pub async fn sleep_until_async(&self, until: Time, context: &context::Context) -> Result<bool> {
+        self.prepare_sleep(until, context)?;
+        loop {
+            let generation = self.wakeup.generation();
+            match self.next_sleep_step(until, context)? {
+                SleepStep::Done(reached) => return Ok(reached),
+                SleepStep::Wait(timeout) => self.wakeup.wait_async(generation, timeout).await,
+            }
+        }
+    }
+
+    /// Asynchronous version of [`Clock::sleep_for`]
+    pub async fn sleep_for_async(
+        &self,
+        duration: duration::Duration,
+        context: &context::Context,
+    ) -> Result<bool> {
+        self.sleep_until_async(self.now()? + duration, context)
+            .await
+    }
+
+    /// Make sure that sleepers are woken up when the time jumps or `context` is shut down.
+    fn prepare_sleep(&self, until: Time, context: &context::Context) -> Result<()> {
+        ensure!(
+            until.clock_type == self.clock_type(),
+            "cannot sleep until a time of a different clock type"
+        );
+
+        let shutdown = context.shutdown_handle();
+        let mut notifiers = self.shutdown_notifiers.lock().unwrap();
+        notifiers.retain(|notifier| notifier.strong_count() > 0);
+        if !notifiers
+            .iter()
+            .any(|notifier| notifier.as_ptr() == Arc::as_ptr(shutdown))
+        {
+            let wakeup = Arc::downgrade(&self.wakeup);
+            context.on_shutdown(move || {
+                if let Some(wakeup) = wakeup.upgrade() {
+                    wakeup.notify();
+                }
+            });
+            notifiers.push(Arc::downgrade(shutdown));
+        }
+        drop(notifiers);
+
+        if self.clock_type() != ClockType::RosTime {
+            return Ok(());
+        }
+        self.sleep_jump_handler.get_or_try_init(|| {
+            let wakeup = Arc::clone(&self.wakeup);
+            let threshold = JumpThreshold::new()
+                .on_clock_change(true)
+                .min_forward(Duration::from_nanos(1))
+                .min_backward(Duration::from_nanos(1));
+            self.on_jump(threshold, || (), move |_| wakeup.notify())
+        })?;
+        Ok(())
+    }
+
+    fn next_sleep_step(&self, until: Time, context: &context::Context) -> Result<SleepStep> {
+        if !context.is_valid() {
+            return Ok(SleepStep::Done(false));
+        }
+
+        let now = self.now()?;
+        if now >= until {
+            return Ok(SleepStep::Done(true));
+        }
+
+        // Simulated time does not advance with the system time, so wait for a jump.
+        let timeout =
+            if self.clock_type() == ClockType::RosTime && self.is_ros_time_override_enabled()? {
+                None
+            } else {
+                Some(Duration::try_from((until - now)?)?)
+            };
+        Ok(SleepStep::Wait(timeout))
+    }
+
+    /// Set the time returned by a ROS clock while the override is enabled.
+    pub fn set_ros_time_override(&self, time: Time) -> Result<()> {
+        self.with_raw_locked(|raw| unsafe {
+            rcl_sys::rcl_set_ros_time_override(raw, time.nanosecs)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_set_ros_time_override in Clock::set_ros_time_override"
+                })
+        })
+    }
 }
 
 #[cfg(test)]
@@ -141,14 +699,77 @@ mod test {
 
     #[test]
     fn ros_clock_now() -> Result<()> {
-        let mut clock = Clock::ros()?;
+        let clock = Clock::ros()?;
         let _now = clock.now()?;
         Ok(())
     }
 
+    #[test]
+    fn ros_clock_override() -> Result<()> {
+        let clock = Clock::ros()?;
+        assert!(!clock.is_ros_time_override_enabled()?);
+
+        clock.enable_ros_time_override()?;
+        clock.set_ros_time_override(Time::from_nanosecs(42, ClockType::RosTime))?;
+        assert!(clock.is_ros_time_override_enabled()?);
+        assert_eq!(clock.now()?.nanosecs, 42);
+
+        clock.disable_ros_time_override()?;
+        assert_ne!(clock.now()?.nanosecs, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn system_clock_override_fails() -> Result<()> {
+        let clock = Clock::system()?;
+        assert!(clock.enable_ros_time_override().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn jump_callbacks() -> Result<()> {
+        use std::sync::Mutex;
+
+        let clock = Clock::ros()?;
+        let pre_count = Arc::new(Mutex::new(0));
+        let jumps = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = {
+            let pre_count = Arc::clone(&pre_count);
+            let jumps = Arc::clone(&jumps);
+            clock.on_jump(
+                JumpThreshold::new()
+                    .on_clock_change(true)
+                    .min_backward(Duration::from_nanos(1)),
+                move || *pre_count.lock().unwrap() += 1,
+                move |jump| jumps.lock().unwrap().push(*jump),
+            )?
+        };
+
+        clock.set_ros_time_override(Time::from_nanosecs(100, ClockType::RosTime))?;
+        clock.enable_ros_time_override()?;
+        clock.set_ros_time_override(Time::from_nanosecs(200, ClockType::RosTime))?;
+        clock.set_ros_time_override(Time::from_nanosecs(50, ClockType::RosTime))?;
+
+        assert_eq!(*pre_count.lock().unwrap(), 2);
+        let jumps = jumps.lock().unwrap().clone();
+        assert_eq!(jumps.len(), 2);
+        assert_eq!(jumps[0].clock_change, ClockChange::RosTimeActivated);
+        assert_eq!(jumps[1].clock_change, ClockChange::RosTimeNoChange);
+        assert_eq!(jumps[1].delta, duration::Duration::from_nanosecs(-150));
+
+        drop(handler);
+        clock.set_ros_time_override(Time::from_nanosecs(10, ClockType::RosTime))?;
+        assert_eq!(*pre_count.lock().unwrap(), 2);
+
+        let num_jump_callbacks = clock.with_raw_locked(|raw| unsafe { (*raw).num_jump_callbacks });
+        assert_eq!(num_jump_callbacks, 0);
+        Ok(())
+    }
+
     #[test]
     fn system_clock_now() -> Result<()> {
-        let mut clock = Clock::system()?;
+        let clock = Clock::system()?;
         let _now = clock.now()?;
 
         Ok(())
@@ -156,7 +777,7 @@ mod test {
 
     #[test]
     fn steady_clock_now() -> Result<()> {
-        let mut clock = Clock::steady()?;
+        let clock = Clock::steady()?;
         let _now = clock.now()?;
         Ok(())
     }
@@ -0,0 +1,436 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Result};
+use rclrust_msg::{
+    composition_interfaces::srv::{
+        ListNodes, ListNodes_Response, LoadNode, LoadNode_Request, LoadNode_Response, UnloadNode,
+        UnloadNode_Response,
+    },
+    rcl_interfaces::msg::{Parameter as RclParameter, ParameterType as RclParameterType},
+};
+
+use super::{Component, ComponentOptions, ComponentRegistry};
+use crate::{
+    error::RclRustError, executor::SharedExecutor, log::LogSeverity, node::Node,
+    node_options::NodeOptions, qos::QoSProfile, rclrust_error, rclrust_info, service::Service,
+};
+
+/// Get the value of an extra argument of `LoadNode`, which must be a boolean.
+fn extra_argument_bool(argument: &RclParameter) -> Result<bool> {
+    if argument.value.type_ == RclParameterType::PARAMETER_BOOL {
+        Ok(argument.value.bool_value)
+    } else {
+        Err(RclRustError::InvalidArgument(format!(
+            "Extra component argument '{}' must be a boolean",
+            argument.name
+        ))
+        .into())
+    }
+}
+
+struct LoadedComponent {
+    full_node_name: String,
+    component: Box<dyn Component>,
+}
+
+struct ContainerState {
+    executor: SharedExecutor,
+    registry: ComponentRegistry,
+    components: BTreeMap<u64, LoadedComponent>,
+    next_unique_id: u64,
+}
+
+impl ContainerState {
+    fn load(
+        &mut self,
+        package_name: &str,
+        plugin_name: &str,
+        node_options: NodeOptions,
+    ) -> Result<u64> {
+        if !self.registry.contains(plugin_name) {
+            self.registry.load_package(package_name, plugin_name)?;
+        }
+        let options = ComponentOptions::with_executor(self.executor.clone(), node_options);
+        let component = self.registry.create(plugin_name, options)?;
+
+        let unique_id = self.next_unique_id;
+        self.next_unique_id += 1;
+        self.components.insert(
+            unique_id,
+            LoadedComponent {
+                full_node_name: component.node().fully_qualified_name(),
+                component,
+            },
+        );
+
+        Ok(unique_id)
+    }
+
+    fn unload(&mut self, unique_id: u64) -> Result<()> {
+        self.components
+            .remove(&unique_id)
+            .map(drop)
+            .ok_or_else(|| RclRustError::ComponentNotLoaded(unique_id).into())
+    }
+
+    fn list(&self) -> Vec<(u64, String)> {
+        self.components
+            .iter()
+            .map(|(&unique_id, loaded)| (unique_id, loaded.full_node_name.clone()))
+            .collect()
+    }
+
+    fn handle_load_node(&mut self, req: LoadNode_Request) -> Result<(u64, String)> {
+        let mut args = vec!["--ros-args".to_string()];
+        if !req.node_name.is_empty() {
+            args.push("-r".into());
+            args.push(format!("__node:={}", req.node_name));
+        }
+        if !req.node_namespace.is_empty() {
+            args.push("-r".into());
+            args.push(format!("__ns:={}", req.node_namespace));
+        }
+        for rule in req.remap_rules {
+            args.push("-r".into());
+            args.push(rule);
+        }
+        let mut node_options = NodeOptions::new()
+            .arguments(args)?
+            .use_global_arguments(false)
+            .parameter_overrides(req.parameters.into_iter().map(Into::into).collect());
+        // Unknown extra arguments are ignored like rclcpp does.
+        for argument in &req.extra_arguments {
+            match argument.name.as_str() {
+                "use_intra_process_comms" => {
+                    node_options =
+                        node_options.use_intra_process_comms(extra_argument_bool(argument)?);
+                }
+                "forward_global_arguments" => {
+                    node_options =
+                        node_options.use_global_arguments(extra_argument_bool(argument)?);
+                }
+                _ => {}
+            }
+        }
+
+        let unique_id = self.load(&req.package_name, &req.plugin_name, node_options)?;
+        let loaded = &self.components[&unique_id];
+
+        if req.log_level > 0 {
+            let result = LogSeverity::try_from_int(req.log_level.into())
+                .ok_or_else(|| anyhow!("Unsupported log level {}", req.log_level))
+                .and_then(|level| loaded.component.node().logger().set_level(level));
+            if let Err(e) = result {
+                self.unload(unique_id)?;
+                return Err(e);
+            }
+        }
+
+        Ok((unique_id, loaded.full_node_name.clone()))
+    }
+}
+
+/// Node which loads components on requests of `ros2 component load`
+///
+/// It serves `~/_container/load_node`, `~/_container/unload_node` and
+/// `~/_container/list_nodes` of `composition_interfaces`. A component is looked up in the
+/// registry given on creation by its plugin name, and otherwise loaded from a shared library
+/// found in the ament index (see [`register_components!`](crate::register_components)).
+pub struct ComponentContainer {
+    state: Arc<Mutex<ContainerState>>,
+    _load_node: Service<LoadNode>,
+    _unload_node: Service<UnloadNode>,
+    _list_nodes: Service<ListNodes>,
+    node: Node,
+}
+
+impl ComponentContainer {
+    /// Serve components with `node`, which also spins all of the loaded components.
+    pub fn new(mut node: Node, registry: ComponentRegistry) -> Result<Self> {
+        let state = Arc::new(Mutex::new(ContainerState {
+            executor: node.shared_executor(),
+            registry,
+            components: BTreeMap::new(),
+            next_unique_id: 1,
+        }));
+        let qos = QoSProfile::services_default();
+
+        let load_node = node.create_service::<LoadNode, _>(
+            "~/_container/load_node",
+            {
+                let state = Arc::clone(&state);
+                let logger = node.logger();
+                move |req| {
+                    let plugin_name = req.plugin_name.clone();
+                    let result = state.lock().unwrap().handle_load_node(req);
+                    match result {
+                        Ok((unique_id, full_node_name)) => {
+                            rclrust_info!(
+                                logger,
+                                "Loaded {} as {} with unique ID {}",
+                                plugin_name,
+                                full_node_name,
+                                unique_id
+                            );
+                            LoadNode_Response {
+                                success: true,
+                                full_node_name,
+                                unique_id,
+                                ..Default::default()
+                            }
+                        }
+                        Err(e) => {
+                            rclrust_error!(logger, "Failed to load {}: {}", plugin_name, e);
+                            LoadNode_Response {
+                                error_message: e.to_string(),
+                                ..Default::default()
+                            }
+                        }
+                    }
+                }
+            },
+            &qos,
+        )?;
+
+        let unload_node = node.create_service::<UnloadNode, _>(
+            "~/_container/unload_node",
+            {
+                let state = Arc::clone(&state);
+                move |req| {
+                    let result = state.lock().unwrap().unload(req.unique_id);
+                    match result {
+                        Ok(()) => UnloadNode_Response {
+                            success: true,
+                            ..Default::default()
+                        },
+                        Err(e) => UnloadNode_Response {
+                            success: false,
+                            error_message: e.to_string(),
+                        },
+                    }
+                }
+            },
+            &qos,
+        )?;
+
+        let list_nodes = node.create_service::<ListNodes, _>(
+            "~/_container/list_nodes",
+            {
+                let state = Arc::clone(&state);
+                move |_req| {
+                    let (unique_ids, full_node_names) =
+                        state.lock().unwrap().list().into_iter().unzip();
+                    ListNodes_Response {
+                        full_node_names,
+                        unique_ids,
+                    }
+                }
+            },
+            &qos,
+        )?;
+
+        Ok(Self {
+            state,
+            _load_node: load_node,
+            _unload_node: unload_node,
+            _list_nodes: list_nodes,
+            node,
+        })
+    }
+
+    /// Get the node of the container.
+    pub const fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Load a component and return its unique ID.
+    ///
+    /// The node name and namespace of the component can be remapped by the arguments of
+    /// `node_options`, e.g. `--ros-args -r __node:=<name>`.
+    pub fn load_component(
+        &self,
+        package_name: &str,
+        plugin_name: &str,
+        node_options: NodeOptions,
+    ) -> Result<u64> {
+        self.state
+            .lock()
+            .unwrap()
+            .load(package_name, plugin_name, node_options)
+    }
+
+    /// Unload the component of `unique_id`.
+    pub fn unload_component(&self, unique_id: u64) -> Result<()> {
+        self.state.lock().unwrap().unload(unique_id)
+    }
+
+    /// Get the unique IDs and the fully qualified node names of the loaded components.
+    pub fn list_components(&self) -> Vec<(u64, String)> {
+        self.state.lock().unwrap().list()
+    }
+
+    /// Block until the executor stops, e.g. on shutdown of the context.
+    pub fn wait(&mut self) {
+        self.node.wait()
+    }
+}
+
+impl Drop for ComponentContainer {
+    fn drop(&mut self) {
+        // Components hold the executor of the container, which stops with the last holder.
+        self.state.lock().unwrap().components.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestComponent {
+        node: Node,
+    }
+
+    impl Component for TestComponent {
+        fn new(options: ComponentOptions) -> Result<Self> {
+            Ok(Self {
+                node: options.create_node("test_component")?,
+            })
+        }
+
+        fn node(&self) -> &Node {
+            &self.node
+        }
+    }
+
+    fn test_registry() -> ComponentRegistry {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<TestComponent>("test::TestComponent");
+        registry
+    }
+
+    #[tokio::test]
+    async fn load_and_unload_component() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_container")?;
+        let container = ComponentContainer::new(node, test_registry())?;
+
+        let first = container.load_component("test", "test::TestComponent", NodeOptions::new())?;
+        let options = NodeOptions::new().arguments(vec![
+            "--ros-args".into(),
+            "-r".into(),
+            "__node:=renamed".into(),
+            "-r".into(),
+            "__ns:=/ns".into(),
+        ])?;
+        let second = container.load_component("test", "test::TestComponent", options)?;
+        assert_eq!(
+            container.list_components(),
+            [
+                (first, "/test_component".to_string()),
+                (second, "/ns/renamed".to_string())
+            ]
+        );
+
+        container.unload_component(first)?;
+        assert_eq!(
+            container.list_components(),
+            [(second, "/ns/renamed".to_string())]
+        );
+        assert!(container.unload_component(first).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_node_request() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_container")?;
+        let container = ComponentContainer::new(node, test_registry())?;
+
+        let req = LoadNode_Request {
+            package_name: "test".into(),
+            plugin_name: "test::TestComponent".into(),
+            node_name: "loaded".into(),
+            remap_rules: vec!["chatter:=talk".into()],
+            parameters: vec![crate::Parameter::integer("answer", 42).into()],
+            ..Default::default()
+        };
+        let (unique_id, full_node_name) = container.state.lock().unwrap().handle_load_node(req)?;
+        assert_eq!(unique_id, 1);
+        assert_eq!(full_node_name, "/loaded");
+
+        let state = container.state.lock().unwrap();
+        let component_node = state.components[&unique_id].component.node();
+        assert_eq!(
+            component_node.resolve_topic_name("chatter", false)?,
+            "/talk"
+        );
+        component_node.declare_parameter("answer", &crate::ParameterValue::integer(0))?;
+        assert_eq!(
+            component_node.get_parameter("answer").unwrap().value,
+            crate::ParameterValue::integer(42)
+        );
+        drop(state);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_node_extra_arguments() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_container")?;
+        let container = ComponentContainer::new(node, test_registry())?;
+
+        let req = LoadNode_Request {
+            package_name: "test".into(),
+            plugin_name: "test::TestComponent".into(),
+            extra_arguments: vec![
+                crate::Parameter::bool("use_intra_process_comms", true).into(),
+                crate::Parameter::bool("forward_global_arguments", true).into(),
+                crate::Parameter::integer("unknown", 1).into(),
+            ],
+            ..Default::default()
+        };
+        container.state.lock().unwrap().handle_load_node(req)?;
+
+        let req = LoadNode_Request {
+            package_name: "test".into(),
+            plugin_name: "test::TestComponent".into(),
+            extra_arguments: vec![crate::Parameter::integer("use_intra_process_comms", 1).into()],
+            ..Default::default()
+        };
+        let error = container
+            .state
+            .lock()
+            .unwrap()
+            .handle_load_node(req)
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RclRustError>(),
+            Some(RclRustError::InvalidArgument(_))
+        ));
+        assert_eq!(container.list_components().len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_unknown_component() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_container")?;
+        let container = ComponentContainer::new(node, test_registry())?;
+
+        let error = container
+            .load_component("unknown_package", "unknown::Component", NodeOptions::new())
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RclRustError>(),
+            Some(RclRustError::ComponentNotFound { .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+//! Entry point of component libraries, exported by
+//! [`register_components!`](crate::register_components)
+
+use std::{
+    ffi::CStr,
+    mem::MaybeUninit,
+    os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use anyhow::{anyhow, Result};
+
+use super::{registry::create_component, Component, ComponentOptions};
+use crate::{error::RclRustError, internal::globals};
+
+/// Name of the function exported by [`register_components!`](crate::register_components),
+/// which returns the [`ComponentTable`] of the library
+pub const REGISTER_COMPONENTS_SYMBOL: &str = "rclrust_components_v1";
+
+/// Version of the layout of [`ComponentTable`] and [`ComponentEntry`]
+pub const COMPONENT_ABI_VERSION: u32 = 1;
+
+/// Version of rclrust, the compiler, the target and the features, which a library must be
+/// built with to share the types of rclrust with the container
+#[doc(hidden)]
+pub const BUILD_ID: &str = concat!(env!("RCLRUST_BUILD_ID"), "\0");
+
+/// Type of the function exported by [`register_components!`](crate::register_components)
+pub(super) type RegisterComponents = unsafe extern "C" fn() -> *const ComponentTable;
+
+/// Function which instantiates a component in a library
+///
+/// It moves `ComponentOptions` out of `options` and writes `Result<Box<dyn Component>>` into
+/// `component`.
+pub type RawComponentFactory = unsafe extern "C" fn(options: *mut c_void, component: *mut c_void);
+
+/// Components exported by a library
+#[repr(C)]
+#[derive(Debug)]
+pub struct ComponentTable {
+    /// [`COMPONENT_ABI_VERSION`] of the library
+    pub abi_version: u32,
+    /// NUL-terminated build ID of rclrust in the library
+    pub build_id: *const c_char,
+    /// Make rclrust in the library use the process-wide state of the container, including the
+    /// async runtime which its entities spawn their tasks on.
+    pub share_globals: unsafe extern "C" fn(globals: *const c_void),
+    pub entries: *const ComponentEntry,
+    pub entries_len: usize,
+}
+
+unsafe impl Sync for ComponentTable {}
+
+impl ComponentTable {
+    /// Check that the library can share the types and the state of rclrust with this process,
+    /// and make it use the state of this process.
+    ///
+    /// # Safety
+    ///
+    /// The table must be returned by the function exported by
+    /// [`register_components!`](crate::register_components).
+    pub(super) unsafe fn link(&self) -> Result<Vec<(String, RawComponentFactory)>> {
+        if self.abi_version != COMPONENT_ABI_VERSION {
+            return Err(RclRustError::ComponentIncompatible(format!(
+                "ABI version {} is not {}",
+                self.abi_version, COMPONENT_ABI_VERSION
+            ))
+            .into());
+        }
+        let build_id = CStr::from_ptr(self.build_id).to_string_lossy();
+        let expected = BUILD_ID.trim_end_matches('\0');
+        if build_id != expected {
+            return Err(RclRustError::ComponentIncompatible(format!(
+                r#"built with "{}", not "{}""#,
+                build_id, expected
+            ))
+            .into());
+        }
+
+        (self.share_globals)(globals::as_ptr());
+
+        slice::from_raw_parts(self.entries, self.entries_len)
+            .iter()
+            .map(|entry| {
+                let plugin_name = CStr::from_ptr(entry.plugin_name).to_str()?;
+                Ok((plugin_name.to_string(), entry.create))
+            })
+            .collect()
+    }
+}
+
+/// Component exported by a library
+#[repr(C)]
+#[derive(Debug)]
+pub struct ComponentEntry {
+    /// NUL-terminated plugin name
+    pub plugin_name: *const c_char,
+    pub create: RawComponentFactory,
+}
+
+unsafe impl Sync for ComponentEntry {}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn share_globals(globals: *const c_void) {
+    globals::share(globals);
+}
+
+/// Instantiate `C` for [`ComponentEntry::create`], catching a panic not to unwind into the
+/// container.
+#[doc(hidden)]
+pub unsafe extern "C" fn create_raw_component<C: Component>(
+    options: *mut c_void,
+    component: *mut c_void,
+) {
+    let options = ptr::read(options as *mut ComponentOptions);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| create_component::<C>(options)))
+        .unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            Err(anyhow!(
+                "the component panicked on its creation: {}",
+                message
+            ))
+        });
+    ptr::write(component as *mut Result<Box<dyn Component>>, result);
+}
+
+/// Call `create` of a library with `options`.
+pub(super) fn call_raw_factory(
+    create: RawComponentFactory,
+    options: ComponentOptions,
+) -> Result<Box<dyn Component>> {
+    let mut options = MaybeUninit::new(options);
+    let mut component = MaybeUninit::<Result<Box<dyn Component>>>::uninit();
+    unsafe {
+        create(
+            options.as_mut_ptr() as *mut c_void,
+            component.as_mut_ptr() as *mut c_void,
+        );
+        component.assume_init()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use super::*;
+    use crate::{node::Node, timer::Timer};
+
+    struct TestComponent {
+        node: Node,
+        _timer: Arc<Timer>,
+    }
+
+    impl Component for TestComponent {
+        fn new(options: ComponentOptions) -> Result<Self> {
+            let mut node = options.create_node("library_component")?;
+            let timer = node.create_wall_timer(Duration::from_millis(100), || ())?;
+            Ok(Self {
+                node,
+                _timer: timer,
+            })
+        }
+
+        fn node(&self) -> &Node {
+            &self.node
+        }
+    }
+
+    crate::register_components! {
+        TestComponent => "test::LibraryComponent",
+    }
+
+    #[test]
+    fn link_component_table() -> Result<()> {
+        let table = unsafe { &*rclrust_components_v1() };
+        let factories = unsafe { table.link()? };
+        assert_eq!(factories.len(), 1);
+        assert_eq!(factories[0].0, "test::LibraryComponent");
+
+        let other_version = ComponentTable {
+            abi_version: COMPONENT_ABI_VERSION + 1,
+            build_id: table.build_id,
+            share_globals: table.share_globals,
+            entries: table.entries,
+            entries_len: table.entries_len,
+        };
+        assert!(unsafe { other_version.link() }.is_err());
+
+        let other_build_id = std::ffi::CString::new("other")?;
+        let other_build = ComponentTable {
+            abi_version: table.abi_version,
+            build_id: other_build_id.as_ptr(),
+            share_globals: table.share_globals,
+            entries: table.entries,
+            entries_len: table.entries_len,
+        };
+        let error = unsafe { other_build.link() }.unwrap_err();
+        match error.downcast_ref::<RclRustError>() {
+            Some(RclRustError::ComponentIncompatible(reason)) => {
+                assert!(reason.contains(r#"built with "other""#), "{}", reason);
+            }
+            _ => panic!("unexpected error: {}", error),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_library_component() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("library_container")?;
+        let (_, create) = unsafe { (*rclrust_components_v1()).link()? }.remove(0);
+
+        let options = ComponentOptions::new(&node, crate::NodeOptions::new());
+        let component = call_raw_factory(create, options)?;
+        assert_eq!(
+            component.node().fully_qualified_name(),
+            "/library_component"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Components, which are nodes instantiated at runtime in a container process
+//!
+//! A component implements [`Component`] and is registered under a plugin name, either directly
+//! to a [`ComponentRegistry`] or by [`register_components!`](crate::register_components) in a
+//! shared library. A [`ComponentContainer`] loads components on requests of
+//! `ros2 component load`, and all of them are spun by the executor of the container.
+//!
+//! # Examples
+//!
+//! ```
+//! use anyhow::Result;
+//! use rclrust::{
+//!     component::{Component, ComponentContainer, ComponentOptions, ComponentRegistry},
+//!     node::Node,
+//!     NodeOptions,
+//! };
+//!
+//! struct Talker {
+//!     node: Node,
+//! }
+//!
+//! impl Component for Talker {
+//!     fn new(options: ComponentOptions) -> Result<Self> {
+//!         Ok(Self {
+//!             node: options.create_node("talker")?,
+//!         })
+//!     }
+//!
+//!     fn node(&self) -> &Node {
+//!         &self.node
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let ctx = rclrust::init().unwrap();
+//! let mut registry = ComponentRegistry::new();
+//! registry.register::<Talker>("examples::Talker");
+//!
+//! let node = ctx.create_node("ComponentManager").unwrap();
+//! let container = ComponentContainer::new(node, registry).unwrap();
+//! let unique_id = container
+//!     .load_component("examples", "examples::Talker", NodeOptions::new())
+//!     .unwrap();
+//! assert_eq!(
+//!     container.list_components(),
+//!     [(unique_id, "/talker".to_string())]
+//! );
+//! # }
+//! ```
+
+mod container;
+mod library;
+mod registry;
+
+use anyhow::Result;
+pub use container::ComponentContainer;
+#[doc(hidden)]
+pub use library::{create_raw_component, share_globals, BUILD_ID};
+pub use library::{
+    ComponentEntry, ComponentTable, RawComponentFactory, COMPONENT_ABI_VERSION,
+    REGISTER_COMPONENTS_SYMBOL,
+};
+pub use registry::{ComponentFactory, ComponentRegistry};
+
+use crate::{executor::SharedExecutor, node::Node, node_options::NodeOptions};
+
+/// Node which can be loaded into a [`ComponentContainer`]
+pub trait Component: Send + 'static {
+    /// Instantiate the component, whose node is created by [`ComponentOptions::create_node`].
+    fn new(options: ComponentOptions) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Get the node of the component.
+    fn node(&self) -> &Node;
+}
+
+/// Options given to [`Component::new`]
+#[derive(Debug)]
+pub struct ComponentOptions {
+    executor: SharedExecutor,
+    node_options: NodeOptions,
+}
+
+impl ComponentOptions {
+    /// Create options for a component which is spun by the executor of `node`.
+    pub fn new(node: &Node, node_options: NodeOptions) -> Self {
+        Self::with_executor(node.shared_executor(), node_options)
+    }
+
+    pub(crate) const fn with_executor(executor: SharedExecutor, node_options: NodeOptions) -> Self {
+        Self {
+            executor,
+            node_options,
+        }
+    }
+
+    /// Get the options of the node, whose arguments carry the name, namespace and remap rules
+    /// requested for the component.
+    pub const fn node_options(&self) -> &NodeOptions {
+        &self.node_options
+    }
+
+    /// Create the node of the component.
+    ///
+    /// `default_name` is used unless the node name is remapped by the node options.
+    pub fn create_node(&self, default_name: &str) -> Result<Node> {
+        Node::with_executor(&self.executor, default_name, None, &self.node_options)
+    }
+
+    /// Create the node of the component with a namespace, like [`Self::create_node`].
+    pub fn create_node_with_ns(&self, default_name: &str, default_namespace: &str) -> Result<Node> {
+        Node::with_executor(
+            &self.executor,
+            default_name,
+            Some(default_namespace),
+            &self.node_options,
+        )
+    }
+}
+
+/// Export components from a shared library, so that a [`ComponentContainer`] can load them.
+///
+/// The library is found with the `rclrust_components` resource of the ament index, whose file
+/// `share/ament_index/resource_index/rclrust_components/<package>` has a line of
+/// `<plugin name>;<library path>` for each component. A relative library path is resolved
+/// from the install prefix.
+///
+/// It exports an `extern "C"` function named [`REGISTER_COMPONENTS_SYMBOL`], which returns
+/// a [`ComponentTable`]. The container rejects the library unless it is built with the same
+/// version and features of rclrust and the same compiler, because the components share the
+/// types of rclrust with the container. Then the copy of rclrust in the library uses the
+/// process-wide state of the container, e.g. the lock of logging and the async runtime, which
+/// the copy of tokio in the library has no context of. The library must not change the
+/// global allocator either.
+///
+/// # Examples
+///
+/// ```ignore
+/// rclrust::register_components! {
+///     my_package::Talker => "my_package::Talker",
+///     my_package::Listener => "my_package::Listener",
+/// }
+/// ```
+#[macro_export]
+macro_rules! register_components {
+    ($($component:ty => $plugin_name:literal),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn rclrust_components_v1() -> *const $crate::component::ComponentTable {
+            static ENTRIES: &[$crate::component::ComponentEntry] = &[$(
+                $crate::component::ComponentEntry {
+                    plugin_name: concat!($plugin_name, "\0").as_ptr() as *const _,
+                    create: $crate::component::create_raw_component::<$component>,
+                },
+            )*];
+            static TABLE: $crate::component::ComponentTable = $crate::component::ComponentTable {
+                abi_version: $crate::component::COMPONENT_ABI_VERSION,
+                build_id: $crate::component::BUILD_ID.as_ptr() as *const _,
+                share_globals: $crate::component::share_globals,
+                entries: ENTRIES.as_ptr(),
+                entries_len: ENTRIES.len(),
+            };
+            &TABLE
+        }
+    };
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use libloading::{Library, Symbol};
+
+use super::{
+    library::{self, RawComponentFactory, RegisterComponents, REGISTER_COMPONENTS_SYMBOL},
+    Component, ComponentOptions,
+};
+use crate::{error::RclRustError, internal::globals};
+
+/// Resource type of the ament index which lists the components of a package
+const RESOURCE_TYPE: &str = "rclrust_components";
+
+/// Function which instantiates a component
+pub type ComponentFactory = fn(ComponentOptions) -> Result<Box<dyn Component>>;
+
+pub(super) fn create_component<C: Component>(
+    options: ComponentOptions,
+) -> Result<Box<dyn Component>> {
+    Ok(Box::new(C::new(options)?))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Factory {
+    Native(ComponentFactory),
+    /// Exported by a library with [`register_components!`](crate::register_components)
+    Library(RawComponentFactory),
+}
+
+/// Components which can be instantiated by their plugin names
+#[derive(Debug, Clone, Default)]
+pub struct ComponentRegistry {
+    factories: BTreeMap<String, Factory>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register `C` under `plugin_name`, replacing the component already registered.
+    pub fn register<C: Component>(&mut self, plugin_name: &str) {
+        self.factories
+            .insert(plugin_name.into(), Factory::Native(create_component::<C>));
+    }
+
+    /// Check if a component is registered under `plugin_name`.
+    pub fn contains(&self, plugin_name: &str) -> bool {
+        self.factories.contains_key(plugin_name)
+    }
+
+    /// Get the plugin names of the registered components in order.
+    pub fn plugin_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.factories.keys().map(|name| name.as_str())
+    }
+
+    /// Instantiate the component registered under `plugin_name`.
+    pub fn create(
+        &self,
+        plugin_name: &str,
+        options: ComponentOptions,
+    ) -> Result<Box<dyn Component>> {
+        let factory = self
+            .factories
+            .get(plugin_name)
+            .ok_or_else(|| RclRustError::ComponentNotRegistered(plugin_name.into()))?;
+        match *factory {
+            Factory::Native(create) => create(options),
+            Factory::Library(create) => library::call_raw_factory(create, options),
+        }
+    }
+
+    /// Register the components exported by a shared library with
+    /// [`register_components!`](crate::register_components).
+    ///
+    /// The library is never unloaded once loaded. It must be built with the same version and
+    /// features of rclrust, the same compiler and the same global allocator as this process,
+    /// which is checked except for the allocator.
+    pub fn load_library(&mut self, path: &Path) -> Result<()> {
+        let mut libraries = globals::get().component_libraries.lock().unwrap();
+        if !libraries.contains_key(path) {
+            let library = unsafe { Library::new(path) }
+                .with_context(|| format!("Failed to load {}", path.display()))?;
+            libraries.insert(path.into(), library);
+        }
+
+        let factories = unsafe {
+            let register: Symbol<'_, RegisterComponents> = libraries[path]
+                .get(REGISTER_COMPONENTS_SYMBOL.as_bytes())
+                .with_context(|| {
+                    format!(
+                        "Failed to find {} in {}",
+                        REGISTER_COMPONENTS_SYMBOL,
+                        path.display()
+                    )
+                })?;
+            register()
+                .as_ref()
+                .with_context(|| format!("No components are exported by {}", path.display()))?
+                .link()
+                .with_context(|| format!("Failed to link {}", path.display()))?
+        };
+        drop(libraries);
+
+        self.factories.extend(
+            factories
+                .into_iter()
+                .map(|(plugin_name, create)| (plugin_name, Factory::Library(create))),
+        );
+
+        Ok(())
+    }
+
+    /// Register the components of the library which provides `plugin_name` of `package_name`,
+    /// looked up in the ament index.
+    pub fn load_package(&mut self, package_name: &str, plugin_name: &str) -> Result<()> {
+        let path = find_library(package_name, plugin_name)?;
+        self.load_library(&path)
+    }
+}
+
+/// Find the library of a component from the `rclrust_components` resources of the ament index.
+fn find_library(package_name: &str, plugin_name: &str) -> Result<PathBuf> {
+    let prefixes = env::var_os("AMENT_PREFIX_PATH").unwrap_or_default();
+    for prefix in env::split_paths(&prefixes) {
+        let resource = prefix
+            .join("share/ament_index/resource_index")
+            .join(RESOURCE_TYPE)
+            .join(package_name);
+        let content = match fs::read_to_string(&resource) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+
+        let library = content
+            .lines()
+            .filter_map(|line| line.split_once(';'))
+            .find(|(name, _)| name.trim() == plugin_name)
+            .map(|(_, library)| prefix.join(library.trim()));
+        if let Some(library) = library {
+            return Ok(library);
+        }
+    }
+
+    Err(RclRustError::ComponentNotFound {
+        package_name: package_name.into(),
+        plugin_name: plugin_name.into(),
+    }
+    .into())
+}
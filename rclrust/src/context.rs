@@ -1,31 +1,48 @@
 use std::{
     ffi::CString,
+    fmt, mem,
     os::raw::c_int,
-    sync::{Arc, Mutex},
+    sync::{Arc, Condvar, Mutex},
 };
 
 use anyhow::{Context as _, Result};
+use futures::channel::oneshot;
 
 use crate::{
+    arguments::Arguments,
     error::ToRclRustResult,
-    init_options::InitOptions,
-    log::{logging_output_handler, Logger, LOGGER_MUTEX},
+    init_options::{self, InitOptions},
+    internal::signal_handler,
+    intra_process::IntraProcessManager,
+    log::{logger_mutex, logging_output_handler, Logger},
     node::Node,
     node_options::NodeOptions,
     rclrust_error,
 };
 
 #[derive(Debug)]
-pub(crate) struct RclContext(Box<rcl_sys::rcl_context_t>);
+pub(crate) struct RclContext {
+    handle: Box<rcl_sys::rcl_context_t>,
+    /// Domain ID applied to nodes, since init options do not have it until galactic.
+    #[cfg(feature = "foxy")]
+    domain_id: Option<usize>,
+    intra_process: Arc<IntraProcessManager>,
+}
 
 unsafe impl Send for RclContext {}
 
 impl RclContext {
-    fn new(args: Vec<String>, init_options: &InitOptions) -> Result<Self> {
+    fn new(args: &[String], init_options: &InitOptions) -> Result<Self> {
         let mut handle = unsafe { Box::new(rcl_sys::rcl_get_zero_initialized_context()) };
+        // `rcl_init` overwrites the enclave of the init options with the one of the arguments.
+        let enclave_args = init_options
+            .security_enclave()
+            .map(|enclave| vec!["--ros-args".to_string(), "--enclave".into(), enclave])
+            .unwrap_or_default();
         let args = args
-            .into_iter()
-            .map(CString::new)
+            .iter()
+            .chain(&enclave_args)
+            .map(|arg| CString::new(arg.as_str()))
             .collect::<std::result::Result<Vec<_>, _>>()?;
         let c_args: Vec<*const _> = args.iter().map(|s| s.as_ptr()).collect();
 
@@ -46,8 +63,8 @@ impl RclContext {
             .to_result()
             .with_context(|| "rcl_sys::rcl_init in RclContext::new")?;
 
-            {
-                let _guard = LOGGER_MUTEX.lock();
+            if init_options.initialize_logging() {
+                let _guard = logger_mutex().lock();
                 rcl_sys::rcl_logging_configure_with_output_handler(
                     &handle.global_arguments,
                     &rcl_sys::rcutils_get_default_allocator(),
@@ -56,18 +73,23 @@ impl RclContext {
                 .to_result()?
             }
 
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                #[cfg(feature = "foxy")]
+                domain_id: init_options.domain_id(),
+                intra_process: Default::default(),
+            })
         }
     }
 
     #[inline]
     const fn raw(&self) -> &rcl_sys::rcl_context_t {
-        &self.0
+        &self.handle
     }
 
     #[inline]
     pub fn raw_mut(&mut self) -> &mut rcl_sys::rcl_context_t {
-        &mut self.0
+        &mut self.handle
     }
 
     pub(crate) fn is_valid(&mut self) -> bool {
@@ -85,9 +107,28 @@ impl RclContext {
         Ok(())
     }
 
+    #[cfg(feature = "foxy")]
+    pub(crate) const fn domain_id(&self) -> Option<usize> {
+        self.domain_id
+    }
+
+    fn security_enclave(&mut self) -> Option<String> {
+        unsafe {
+            let options = rcl_sys::rcl_context_get_init_options(self.raw_mut());
+            if options.is_null() {
+                return None;
+            }
+            init_options::enclave_of(init_options::rmw_init_options(options))
+        }
+    }
+
     pub(crate) const fn global_arguments(&self) -> &rcl_sys::rcl_arguments_t {
         &self.raw().global_arguments
     }
+
+    pub(crate) fn intra_process_manager(&self) -> Arc<IntraProcessManager> {
+        Arc::clone(&self.intra_process)
+    }
 }
 
 impl Drop for RclContext {
@@ -109,31 +150,196 @@ impl Drop for RclContext {
     }
 }
 
+type ShutdownCallback = Box<dyn FnOnce() + Send>;
+
+/// Shuts down a context and notifies it, which is shared with the signal handler.
+pub(crate) struct ContextShutdown {
+    handle: Arc<Mutex<RclContext>>,
+    reason: Mutex<Option<String>>,
+    condvar: Condvar,
+    callbacks: Mutex<Vec<ShutdownCallback>>,
+}
+
+impl fmt::Debug for ContextShutdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContextShutdown {{ reason: {:?} }}", self.reason)
+    }
+}
+
+impl ContextShutdown {
+    fn new(handle: Arc<Mutex<RclContext>>) -> Self {
+        Self {
+            handle,
+            reason: Default::default(),
+            condvar: Condvar::new(),
+            callbacks: Default::default(),
+        }
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        self.handle.lock().unwrap().is_valid()
+    }
+
+    pub(crate) fn shutdown(&self, reason: String) -> Result<()> {
+        let mut current_reason = self.reason.lock().unwrap();
+        if current_reason.is_some() {
+            return Ok(());
+        }
+        self.handle.lock().unwrap().shutdown()?;
+        *current_reason = Some(reason);
+        drop(current_reason);
+        self.condvar.notify_all();
+
+        let callbacks = mem::take(&mut *self.callbacks.lock().unwrap());
+        callbacks.into_iter().for_each(|callback| callback());
+
+        Ok(())
+    }
+
+    fn on_shutdown(&self, callback: ShutdownCallback) {
+        let reason = self.reason.lock().unwrap();
+        if reason.is_some() {
+            drop(reason);
+            callback();
+        } else {
+            self.callbacks.lock().unwrap().push(callback);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Context {
     pub(crate) handle: Arc<Mutex<RclContext>>,
-    shutdown_reason: Mutex<Option<String>>,
+    shutdown: Arc<ContextShutdown>,
+    args: Vec<String>,
 }
 
 impl Context {
     pub(crate) fn new(args: Vec<String>, init_options: InitOptions) -> Result<Self> {
-        let handle = RclContext::new(args, &init_options)?;
+        let handle = Arc::new(Mutex::new(RclContext::new(&args, &init_options)?));
+        let shutdown = Arc::new(ContextShutdown::new(Arc::clone(&handle)));
+        if init_options.shutdown_on_sigint() {
+            signal_handler::register(&shutdown)?;
+        }
 
         Ok(Self {
-            handle: Arc::new(Mutex::new(handle)),
-            shutdown_reason: Default::default(),
+            handle,
+            shutdown,
+            args,
         })
     }
 
+    /// Get the command line arguments given on initialization, which are applied to all nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let args = ["my_node", "--ros-args", "-r", "__ns:=/ns"];
+    /// let ctx = rclrust::init_with_args(
+    ///     args.iter().map(|s| s.to_string()).collect(),
+    ///     rclrust::InitOptions::new().unwrap(),
+    /// )
+    /// .unwrap();
+    /// let arguments = ctx.arguments().unwrap();
+    /// assert_eq!(
+    ///     arguments.remap_node_namespace("my_node").unwrap(),
+    ///     Some("/ns".to_string())
+    /// );
+    /// ```
+    pub fn arguments(&self) -> Result<Arguments> {
+        let handle = self.handle.lock().unwrap();
+        Arguments::from_raw(handle.global_arguments(), self.args.clone())
+    }
+
     pub fn is_valid(&self) -> bool {
-        self.handle.lock().unwrap().is_valid()
+        self.shutdown.is_valid()
     }
 
-    pub(crate) fn shutdown(&self, reason: impl Into<String>) -> Result<()> {
-        self.handle.lock().unwrap().shutdown()?;
-        *self.shutdown_reason.lock().unwrap() = Some(reason.into());
+    /// Get the security enclave used by the context, which is `/` unless it is given by
+    /// [`InitOptions::set_security_enclave`] or `--enclave` in the arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// assert_eq!(ctx.security_enclave().as_deref(), Some("/"));
+    /// ```
+    pub fn security_enclave(&self) -> Option<String> {
+        self.handle.lock().unwrap().security_enclave()
+    }
 
-        Ok(())
+    pub(crate) const fn shutdown_handle(&self) -> &Arc<ContextShutdown> {
+        &self.shutdown
+    }
+
+    /// Shut down the context, which stops the executors of its nodes and calls the callbacks
+    /// registered by [`Context::on_shutdown`]. Shutting down again does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// ctx.shutdown("finished").unwrap();
+    /// assert!(!ctx.is_valid());
+    /// assert_eq!(ctx.shutdown_reason().as_deref(), Some("finished"));
+    /// ```
+    pub fn shutdown(&self, reason: impl Into<String>) -> Result<()> {
+        self.shutdown.shutdown(reason.into())
+    }
+
+    /// Get the reason given on shutdown, or `None` if the context has not been shut down.
+    pub fn shutdown_reason(&self) -> Option<String> {
+        self.shutdown.reason.lock().unwrap().clone()
+    }
+
+    /// Register a callback called once when the context is shut down, e.g. by a signal.
+    ///
+    /// If the context has already been shut down, the callback is called immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// ctx.on_shutdown(|| println!("shutting down"));
+    /// ctx.shutdown("finished").unwrap();
+    /// ```
+    pub fn on_shutdown<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shutdown.on_shutdown(Box::new(callback))
+    }
+
+    /// Block the current thread until the context is shut down.
+    pub fn wait_for_shutdown(&self) {
+        let reason = self.shutdown.reason.lock().unwrap();
+        let _reason = self
+            .shutdown
+            .condvar
+            .wait_while(reason, |reason| reason.is_none())
+            .unwrap();
+    }
+
+    /// Wait until the context is shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let ctx = rclrust::init().unwrap();
+    /// ctx.shutdown("finished").unwrap();
+    /// ctx.shutdown_future().await;
+    /// # }
+    /// ```
+    pub fn shutdown_future(&self) -> impl std::future::Future<Output = ()> {
+        let (tx, rx) = oneshot::channel();
+        self.on_shutdown(move || {
+            let _ = tx.send(());
+        });
+        async move {
+            let _ = rx.await;
+        }
     }
 
     /// Create node with empty namespace and default options
@@ -228,4 +434,56 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn shutdown_with_reason() -> Result<()> {
+        let ctx = crate::init()?;
+        assert_eq!(ctx.shutdown_reason(), None);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        ctx.on_shutdown(move || tx.send(()).unwrap());
+        ctx.shutdown("first")?;
+        ctx.shutdown("second")?;
+        assert!(!ctx.is_valid());
+        assert_eq!(ctx.shutdown_reason().as_deref(), Some("first"));
+        assert_eq!(rx.try_iter().count(), 1);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        ctx.on_shutdown(move || tx.send(()).unwrap());
+        assert_eq!(rx.try_iter().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn wait_for_shutdown() -> Result<()> {
+        let ctx = crate::init()?;
+        std::thread::scope(|s| {
+            s.spawn(|| ctx.shutdown("test").unwrap());
+            ctx.wait_for_shutdown();
+        });
+        assert!(!ctx.is_valid());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shutdown_future() -> Result<()> {
+        let ctx = crate::init()?;
+        let shutdown = ctx.shutdown_future();
+        ctx.shutdown("test")?;
+        shutdown.await;
+
+        Ok(())
+    }
+
+    #[test]
+    fn drop_node_after_shutdown() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_node")?;
+        ctx.shutdown("test")?;
+        drop(node);
+
+        Ok(())
+    }
 }
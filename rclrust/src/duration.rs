@@ -0,0 +1,282 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    time,
+};
+
+use rclrust_msg::builtin_interfaces;
+
+use crate::error::RclRustError;
+
+pub(crate) const S_TO_NS: i64 = 1_000_000_000;
+
+/// Split nanoseconds into seconds and non-negative nanoseconds like ROS messages.
+///
+/// The seconds of ROS messages are `i32`, so it saturates at the maximum and minimum of them.
+pub(crate) fn split_nanosecs(nanosecs: i64) -> (i32, u32) {
+    let sec = nanosecs.div_euclid(S_TO_NS);
+    // `rem_euclid()` is always in `0..S_TO_NS`.
+    let nanosec = nanosecs.rem_euclid(S_TO_NS) as u32;
+    match i32::try_from(sec) {
+        Ok(sec) => (sec, nanosec),
+        Err(_) if sec > 0 => (i32::MAX, (S_TO_NS - 1) as u32),
+        Err(_) => (i32::MIN, 0),
+    }
+}
+
+/// Signed span of time in ROS
+///
+/// Unlike [`std::time::Duration`], it can be negative, e.g. the difference between two
+/// [`Time`](crate::Time)s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    pub nanosecs: i64,
+}
+
+impl Duration {
+    /// Zero duration
+    pub const ZERO: Self = Self::from_nanosecs(0);
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    ///
+    /// let duration = Duration::new(-1, 500_000_000);
+    /// assert_eq!(duration.nanosecs, -500_000_000);
+    /// ```
+    pub fn new(secs: i32, nanosecs: u32) -> Self {
+        Self {
+            nanosecs: i64::from(secs) * S_TO_NS + i64::from(nanosecs),
+        }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    ///
+    /// let duration = Duration::from_nanosecs(-100);
+    /// ```
+    pub const fn from_nanosecs(nanosecs: i64) -> Self {
+        Self { nanosecs }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    ///
+    /// let duration = Duration::from_secs_f64(-1.5);
+    /// assert_eq!(duration.nanosecs, -1_500_000_000);
+    /// ```
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self {
+            nanosecs: (secs * S_TO_NS as f64).round() as i64,
+        }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    ///
+    /// let duration = Duration::from_nanosecs(-1_500_000_000);
+    /// assert_eq!(duration.to_secs_f64(), -1.5);
+    /// ```
+    pub fn to_secs_f64(self) -> f64 {
+        self.nanosecs as f64 / S_TO_NS as f64
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    /// use rclrust_msg::builtin_interfaces;
+    ///
+    /// let duration_msg = builtin_interfaces::msg::Duration {
+    ///     sec: -1,
+    ///     nanosec: 10,
+    /// };
+    /// let duration = Duration::from_ros_msg(&duration_msg);
+    /// assert_eq!(duration, Duration::new(-1, 10));
+    /// ```
+    pub fn from_ros_msg(duration_msg: &builtin_interfaces::msg::Duration) -> Self {
+        Self::new(duration_msg.sec, duration_msg.nanosec)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::Duration;
+    /// use rclrust_msg::builtin_interfaces;
+    ///
+    /// let duration = Duration::from_nanosecs(-1);
+    /// let duration_msg = builtin_interfaces::msg::Duration {
+    ///     sec: -1,
+    ///     nanosec: 999_999_999,
+    /// };
+    /// assert_eq!(duration.to_ros_msg(), duration_msg);
+    /// ```
+    pub fn to_ros_msg(self) -> builtin_interfaces::msg::Duration {
+        let (sec, nanosec) = split_nanosecs(self.nanosecs);
+        builtin_interfaces::msg::Duration { sec, nanosec }
+    }
+
+    /// Add durations, returning `None` on overflow.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.nanosecs.checked_add(rhs.nanosecs) {
+            Some(nanosecs) => Some(Self { nanosecs }),
+            None => None,
+        }
+    }
+
+    /// Subtract durations, returning `None` on overflow.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.nanosecs.checked_sub(rhs.nanosecs) {
+            Some(nanosecs) => Some(Self { nanosecs }),
+            None => None,
+        }
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Duration {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            nanosecs: -self.nanosecs,
+        }
+    }
+}
+
+impl Mul<i64> for Duration {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            nanosecs: self
+                .nanosecs
+                .checked_mul(rhs)
+                .expect("overflow when multiplying duration"),
+        }
+    }
+}
+
+impl TryFrom<time::Duration> for Duration {
+    type Error = RclRustError;
+
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use rclrust::Duration;
+    ///
+    /// let duration = Duration::try_from(std::time::Duration::from_millis(1)).unwrap();
+    /// assert_eq!(duration.nanosecs, 1_000_000);
+    /// ```
+    fn try_from(duration: time::Duration) -> Result<Self, Self::Error> {
+        let nanosecs = duration.as_nanos().try_into().map_err(|_| {
+            RclRustError::OutOfRange(format!("{:?} does not fit in a ROS duration", duration))
+        })?;
+        Ok(Self { nanosecs })
+    }
+}
+
+impl TryFrom<Duration> for time::Duration {
+    type Error = RclRustError;
+
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use rclrust::Duration;
+    ///
+    /// let duration = std::time::Duration::try_from(Duration::from_nanosecs(1_000)).unwrap();
+    /// assert_eq!(duration, std::time::Duration::from_micros(1));
+    /// assert!(std::time::Duration::try_from(Duration::from_nanosecs(-1)).is_err());
+    /// ```
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        let nanosecs = duration
+            .nanosecs
+            .try_into()
+            .map_err(|_| RclRustError::OutOfRange(format!("{:?} is negative", duration)))?;
+        Ok(Self::from_nanos(nanosecs))
+    }
+}
+
+impl From<Duration> for rcl_sys::rcl_duration_t {
+    fn from(duration: Duration) -> Self {
+        Self {
+            nanoseconds: duration.nanosecs,
+        }
+    }
+}
+
+impl From<&rcl_sys::rcl_duration_t> for Duration {
+    fn from(duration: &rcl_sys::rcl_duration_t) -> Self {
+        Self::from_nanosecs(duration.nanoseconds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_negative_nanosecs() {
+        assert_eq!(split_nanosecs(-1), (-1, 999_999_999));
+        assert_eq!(split_nanosecs(-S_TO_NS), (-1, 0));
+        assert_eq!(split_nanosecs(S_TO_NS + 1), (1, 1));
+    }
+
+    #[test]
+    fn split_saturating_nanosecs() {
+        assert_eq!(split_nanosecs(i64::MAX), (i32::MAX, 999_999_999));
+        assert_eq!(split_nanosecs(i64::MIN), (i32::MIN, 0));
+    }
+
+    #[test]
+    fn duration_arithmetic() {
+        let a = Duration::from_nanosecs(300);
+        let b = Duration::from_nanosecs(500);
+        assert_eq!(a - b, Duration::from_nanosecs(-200));
+        assert_eq!(a + b, Duration::from_nanosecs(800));
+        assert_eq!(-a, Duration::from_nanosecs(-300));
+        assert_eq!(a * 3, Duration::from_nanosecs(900));
+        assert!(a < b);
+        assert_eq!(Duration::from_nanosecs(i64::MAX).checked_add(a), None);
+    }
+}
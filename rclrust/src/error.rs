@@ -2,7 +2,7 @@ use std::fmt;
 
 use anyhow::Result;
 
-use crate::internal::ffi::*;
+use crate::{internal::ffi::*, names::NameKind};
 
 #[derive(Debug)]
 pub struct RclErrorBase {
@@ -139,6 +139,36 @@ pub enum RclRustError {
     MessageQueueIsFull { type_: &'static str, name: String },
     #[error("Out of range: {0}")]
     OutOfRange(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Invalid type name: {0}")]
+    InvalidTypeName(String),
+    #[error(r#"Invalid {kind} "{name}": {reason}, at index {invalid_index}"#)]
+    InvalidName {
+        kind: NameKind,
+        name: String,
+        reason: String,
+        invalid_index: usize,
+    },
+
+    // Component
+    #[error(r#"Component "{0}" is not registered"#)]
+    ComponentNotRegistered(String),
+    #[error(
+        r#"Component "{plugin_name}" of package "{package_name}" is not found in the ament index"#
+    )]
+    ComponentNotFound {
+        package_name: String,
+        plugin_name: String,
+    },
+    #[error("No component is loaded with unique ID {0}")]
+    ComponentNotLoaded(u64),
+    #[error("Component library is incompatible with the container: {0}")]
+    ComponentIncompatible(String),
+
+    // Intra-process communication
+    #[error("Intra-process communication is not supported: {0}")]
+    IntraProcessUnsupported(String),
 
     // Parameter
     #[error(r#"Parameter "{name}" cannot be set because it was not declared."#)]
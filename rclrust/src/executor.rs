@@ -1,6 +1,6 @@
 use std::{
     sync::{Arc, Mutex},
-    thread,
+    thread::{self, JoinHandle},
     time::Duration,
 };
 
@@ -9,9 +9,9 @@ use derive_new::new;
 use futures::channel::mpsc;
 
 use crate::{
-    client::ClientInvokerBase, context::RclContext, error::RclRustError,
-    service::ServiceInvokerBase, subscription::SubscriptionInvokerBase, timer::TimerInvoker,
-    wait_set::RclWaitSet,
+    client::ClientInvokerBase, context::RclContext, error::RclRustError, log::Logger,
+    rclrust_error, service::ServiceInvokerBase, subscription::SubscriptionInvokerBase,
+    timer::TimerInvoker, wait_set::RclWaitSet,
 };
 
 #[derive(Debug)]
@@ -20,13 +20,73 @@ pub(super) enum ExecutorMessage {
     Timer(TimerInvoker),
     Client(Box<dyn ClientInvokerBase + Send>),
     Service(Box<dyn ServiceInvokerBase + Send>),
-    Terminate,
+}
+
+/// Thread which runs the executor of a node, shared by the node and its sub-nodes
+///
+/// The executor is terminated when the last of them is dropped.
+#[derive(Debug)]
+pub(super) struct ExecutorThread {
+    tx: Mutex<mpsc::UnboundedSender<ExecutorMessage>>,
+    thread: Mutex<Option<JoinHandle<Result<()>>>>,
+}
+
+impl ExecutorThread {
+    pub fn spawn(
+        context: Arc<Mutex<RclContext>>,
+    ) -> (Self, mpsc::UnboundedSender<ExecutorMessage>) {
+        // Unbounded, so that entities can be created in callbacks run by the executor itself.
+        let (tx, rx) = mpsc::unbounded();
+        let thread = thread::spawn(move || {
+            let mut executor = Executor::new(context, rx);
+            executor.spin()
+        });
+
+        let executor_thread = Self {
+            tx: Mutex::new(tx.clone()),
+            thread: Mutex::new(Some(thread)),
+        };
+        (executor_thread, tx)
+    }
+
+    /// Block until the executor stops, e.g. on shutdown of the context.
+    pub fn join(&self) {
+        let thread = self.thread.lock().unwrap().take();
+        if let Some(thread) = thread {
+            match thread.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => rclrust_error!(
+                    Logger::new("rclrust"),
+                    "Executor of a node stopped with an error: {}",
+                    e
+                ),
+                Err(_) => rclrust_error!(Logger::new("rclrust"), "Executor of a node panicked"),
+            }
+        }
+    }
+}
+
+impl Drop for ExecutorThread {
+    fn drop(&mut self) {
+        // Closing the channel stops the executor even if the channel is full, and it does
+        // nothing if the executor has already stopped, e.g. after the context is shut down.
+        self.tx.get_mut().unwrap().close_channel();
+        self.join();
+    }
+}
+
+/// Executor of a node, which nodes created later can share
+#[derive(Debug, Clone)]
+pub(super) struct SharedExecutor {
+    pub context: Arc<Mutex<RclContext>>,
+    pub thread: Arc<ExecutorThread>,
+    pub tx: mpsc::UnboundedSender<ExecutorMessage>,
 }
 
 #[derive(new)]
 pub(super) struct Executor {
     context: Arc<Mutex<RclContext>>,
-    rx: mpsc::Receiver<ExecutorMessage>,
+    rx: mpsc::UnboundedReceiver<ExecutorMessage>,
     #[new(default)]
     subscriptions: Vec<Box<dyn SubscriptionInvokerBase + Send>>,
     #[new(default)]
@@ -48,7 +108,7 @@ impl Executor {
                     Ok(Some(ExecutorMessage::Timer(v))) => self.timers.push(v),
                     Ok(Some(ExecutorMessage::Client(v))) => self.clients.push(v),
                     Ok(Some(ExecutorMessage::Service(v))) => self.services.push(v),
-                    Ok(Some(ExecutorMessage::Terminate)) => return Ok(()),
+                    // All the senders are dropped or the channel is closed on termination.
                     Ok(None) => return Ok(()),
                     Err(_) => break,
                 }
@@ -61,6 +121,8 @@ impl Executor {
                         thread::sleep(max_duration);
                         continue;
                     }
+                    // Waiting fails once the context is shut down, which just ends spinning.
+                    _ if !self.context.lock().unwrap().is_valid() => return Ok(()),
                     _ => return Err(e),
                 }
             }
@@ -1,4 +1,6 @@
-use anyhow::{Context, Result};
+use std::ffi::{CStr, CString};
+
+use anyhow::{ensure, Context, Result};
 
 use crate::{error::ToRclRustResult, log::Logger, rclrust_error};
 
@@ -24,6 +26,58 @@ impl RclInitOptions {
     pub const fn raw(&self) -> &rcl_sys::rcl_init_options_t {
         &self.0
     }
+
+    fn rmw(&self) -> &rcl_sys::rmw_init_options_t {
+        unsafe { rmw_init_options(&self.0) }
+    }
+
+    fn rmw_mut(&mut self) -> &mut rcl_sys::rmw_init_options_t {
+        unsafe { &mut *rcl_sys::rcl_init_options_get_rmw_init_options(&mut self.0) }
+    }
+
+    fn set_enclave(&mut self, enclave: &str) -> Result<()> {
+        let enclave = CString::new(enclave)?;
+        let allocator = unsafe { rcl_sys::rcl_init_options_get_allocator(&self.0).as_ref() }
+            .copied()
+            .context("rcl_sys::rcl_init_options_get_allocator in RclInitOptions::set_enclave")?;
+        let new_enclave = unsafe { rcl_sys::rcutils_strdup(enclave.as_ptr(), allocator) };
+        ensure!(
+            !new_enclave.is_null(),
+            "failed to allocate the enclave name"
+        );
+
+        let rmw = self.rmw_mut();
+        if !rmw.enclave.is_null() {
+            unsafe {
+                let deallocate = allocator.deallocate.unwrap();
+                deallocate(rmw.enclave.cast(), allocator.state);
+            }
+        }
+        rmw.enclave = new_enclave;
+
+        Ok(())
+    }
+}
+
+/// Get the rmw init options of `options`.
+///
+/// # Safety
+///
+/// `options` must be initialized, and the result must not outlive it.
+pub(crate) unsafe fn rmw_init_options<'a>(
+    options: *const rcl_sys::rcl_init_options_t,
+) -> &'a rcl_sys::rmw_init_options_t {
+    // `rcl_init_options_get_rmw_init_options` only reads the handle, so casting it is safe.
+    &*rcl_sys::rcl_init_options_get_rmw_init_options(options as *mut _)
+}
+
+/// Get the enclave of rmw init options, which is null until it is set.
+pub(crate) fn enclave_of(rmw: &rcl_sys::rmw_init_options_t) -> Option<String> {
+    (!rmw.enclave.is_null()).then(|| {
+        unsafe { CStr::from_ptr(rmw.enclave) }
+            .to_string_lossy()
+            .into_owned()
+    })
 }
 
 impl Drop for RclInitOptions {
@@ -41,16 +95,20 @@ impl Drop for RclInitOptions {
 #[derive(Debug)]
 pub struct InitOptions {
     options: RclInitOptions,
-    /* shutdown_on_sigint: bool,
-     * initialize_logging: bool, */
+    shutdown_on_sigint: bool,
+    initialize_logging: bool,
+    #[cfg(feature = "foxy")]
+    domain_id: Option<usize>,
 }
 
 impl InitOptions {
     pub fn new() -> Result<Self> {
         Ok(Self {
             options: RclInitOptions::new()?,
-            /* shutdown_on_sigint: true,
-             * initialize_logging: true, */
+            shutdown_on_sigint: true,
+            initialize_logging: true,
+            #[cfg(feature = "foxy")]
+            domain_id: None,
         })
     }
 
@@ -58,43 +116,201 @@ impl InitOptions {
         self.options.raw()
     }
 
-    // / # Examples
-    // / ```
-    // / use rclrust::InitOptions;
-    // /
-    // / let init_options = InitOptions::new().unwrap();
-    // / assert_eq!(init_options.initialize_logging(), true);
-    // / ```
-    // pub const fn initialize_logging(&self) -> bool {
-    //     self.initialize_logging
-    // }
-
-    // / # Examples
-    // / ```
-    // / use rclrust::InitOptions;
-    // /
-    // / let init_options = InitOptions::new().unwrap().set_initialize_logging(false);
-    // / assert_eq!(init_options.initialize_logging(), false);
-    // / ```
-    // #[allow(clippy::missing_const_for_fn)]
-    // pub fn set_initialize_logging(self, initialize_logging: bool) -> Self {
-    //     Self {
-    //         initialize_logging,
-    //         ..self
-    //     }
-    // }
-
-    // pub const fn shutdown_on_sigint(&self) -> bool {
-    //     self.shutdown_on_sigint
-    // }
-
-    // #[allow(clippy::missing_const_for_fn)]
-    // pub fn set_shutdown_on_sigint(self, shutdown_on_sigint: bool) -> Self {
-    //     Self {
-    //         shutdown_on_sigint,
-    //         ..self
-    //     }
-    // }
+    /// Check if the logging system is configured from the arguments on initialization.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.initialize_logging(), true);
+    /// ```
+    pub const fn initialize_logging(&self) -> bool {
+        self.initialize_logging
+    }
+
+    /// Set whether the logging system is configured from the arguments on initialization.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap().set_initialize_logging(false);
+    /// assert_eq!(init_options.initialize_logging(), false);
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn set_initialize_logging(self, initialize_logging: bool) -> Self {
+        Self {
+            initialize_logging,
+            ..self
+        }
+    }
+
+    /// Check if the context is shut down on SIGINT or SIGTERM.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.shutdown_on_sigint(), true);
+    /// ```
+    pub const fn shutdown_on_sigint(&self) -> bool {
+        self.shutdown_on_sigint
+    }
+
+    /// Set whether the context is shut down on SIGINT or SIGTERM.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap().set_shutdown_on_sigint(false);
+    /// assert_eq!(init_options.shutdown_on_sigint(), false);
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn set_shutdown_on_sigint(self, shutdown_on_sigint: bool) -> Self {
+        Self {
+            shutdown_on_sigint,
+            ..self
+        }
+    }
+
+    /// Get the domain ID, or `None` if it is given by the `ROS_DOMAIN_ID` environment variable.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.domain_id(), None);
+    /// ```
+    #[cfg(feature = "foxy")]
+    pub const fn domain_id(&self) -> Option<usize> {
+        self.domain_id
+    }
+
+    /// Get the domain ID, or `None` if it is given by the `ROS_DOMAIN_ID` environment variable.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.domain_id(), None);
+    /// ```
+    #[cfg(feature = "galactic+")]
+    pub fn domain_id(&self) -> Option<usize> {
+        match self.options.rmw().domain_id {
+            rcl_sys::RMW_DEFAULT_DOMAIN_ID => None,
+            domain_id => Some(domain_id),
+        }
+    }
+
+    /// Set the domain ID, which overrides the `ROS_DOMAIN_ID` environment variable.
+    ///
+    /// Nodes in contexts with different domain IDs do not communicate with each other.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap().set_domain_id(42);
+    /// assert_eq!(init_options.domain_id(), Some(42));
+    /// ```
+    #[allow(unused_mut, clippy::missing_const_for_fn)]
+    pub fn set_domain_id(mut self, domain_id: usize) -> Self {
+        #[cfg(feature = "foxy")]
+        {
+            self.domain_id = Some(domain_id);
+        }
+        #[cfg(feature = "galactic+")]
+        {
+            self.options.rmw_mut().domain_id = domain_id;
+        }
+        self
+    }
+
+    /// Get whether communication is limited to localhost, or `None` if it is given by the
+    /// `ROS_LOCALHOST_ONLY` environment variable.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.localhost_only(), None);
+    /// ```
+    #[cfg(feature = "galactic+")]
+    pub fn localhost_only(&self) -> Option<bool> {
+        use rcl_sys::rmw_localhost_only_t::*;
+
+        match self.options.rmw().localhost_only {
+            RMW_LOCALHOST_ONLY_DEFAULT => None,
+            RMW_LOCALHOST_ONLY_ENABLED => Some(true),
+            RMW_LOCALHOST_ONLY_DISABLED => Some(false),
+        }
+    }
+
+    /// Set whether communication is limited to localhost, which overrides the
+    /// `ROS_LOCALHOST_ONLY` environment variable.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap().set_localhost_only(true);
+    /// assert_eq!(init_options.localhost_only(), Some(true));
+    /// ```
+    #[cfg(feature = "galactic+")]
+    pub fn set_localhost_only(mut self, localhost_only: bool) -> Self {
+        use rcl_sys::rmw_localhost_only_t::*;
+
+        self.options.rmw_mut().localhost_only = if localhost_only {
+            RMW_LOCALHOST_ONLY_ENABLED
+        } else {
+            RMW_LOCALHOST_ONLY_DISABLED
+        };
+        self
+    }
+
+    /// Get the security enclave, or `None` if it is not set.
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new().unwrap();
+    /// assert_eq!(init_options.security_enclave(), None);
+    /// ```
+    pub fn security_enclave(&self) -> Option<String> {
+        enclave_of(self.options.rmw())
+    }
+
+    /// Set the security enclave, the name used to find security artifacts in a keystore.
+    ///
+    /// `rcl_init` takes the enclave from the arguments, so it is passed as
+    /// `--ros-args --enclave <enclave>` after the arguments given on initialization, which
+    /// overrides `--enclave` in them. The enclave of the context is given by
+    /// [`Context::security_enclave`](crate::Context::security_enclave).
+    ///
+    /// # Examples
+    /// ```
+    /// use rclrust::InitOptions;
+    ///
+    /// let init_options = InitOptions::new()
+    ///     .unwrap()
+    ///     .set_security_enclave("/my_enclave")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     init_options.security_enclave().as_deref(),
+    ///     Some("/my_enclave")
+    /// );
+    /// ```
+    pub fn set_security_enclave(mut self, enclave: &str) -> Result<Self> {
+        self.options.set_enclave(enclave)?;
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
@@ -107,4 +323,28 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn init_with_domain_id() -> Result<()> {
+        let init_options = InitOptions::new()?
+            .set_domain_id(42)
+            .set_security_enclave("/test")?
+            .set_security_enclave("/test2")?;
+        assert_eq!(init_options.domain_id(), Some(42));
+        assert_eq!(init_options.security_enclave().as_deref(), Some("/test2"));
+
+        let ctx = crate::init_with_options(init_options)?;
+        let _node = ctx.create_node("domain_node")?;
+        assert_eq!(ctx.security_enclave().as_deref(), Some("/test2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_default_enclave() -> Result<()> {
+        let ctx = crate::init_with_options(InitOptions::new()?)?;
+        assert_eq!(ctx.security_enclave().as_deref(), Some("/"));
+
+        Ok(())
+    }
 }
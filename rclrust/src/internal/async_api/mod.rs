@@ -1,2 +1,31 @@
 mod tokio;
-pub use self::tokio::*;
+
+use std::{future::Future, time::Duration};
+
+use futures::future::BoxFuture;
+
+use super::globals;
+
+/// Functions of the async runtime
+///
+/// They are called through the process-wide state, so that a component library spawns its
+/// tasks on the runtime of the container instead of its own copy of the runtime, which has no
+/// context in the library.
+pub struct Runtime {
+    pub spawn: fn(BoxFuture<'static, ()>),
+    pub sleep: fn(Duration) -> BoxFuture<'static, ()>,
+}
+
+pub use self::tokio::RUNTIME;
+
+/// Spawn a detached task on the runtime.
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    (globals::get().runtime.spawn)(Box::pin(future))
+}
+
+pub fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    (globals::get().runtime.sleep)(duration)
+}
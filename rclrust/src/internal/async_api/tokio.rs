@@ -1 +1,6 @@
-pub use tokio::{spawn, task::JoinHandle};
+use super::Runtime;
+
+pub const RUNTIME: Runtime = Runtime {
+    spawn: |future| drop(tokio::spawn(future)),
+    sleep: |duration| Box::pin(tokio::time::sleep(duration)),
+};
@@ -38,3 +38,18 @@ unsafe impl FromCChar for str {
         CStr::from_c_char(ptr).map(|v| v.to_str().expect("expect UTF-8 string"))
     }
 }
+
+/// Free memory which rcl allocated with the default allocator.
+pub unsafe fn deallocate<T>(pointer: *mut T) {
+    let allocator = rcl_sys::rcutils_get_default_allocator();
+    if let Some(deallocate) = allocator.deallocate {
+        deallocate(pointer.cast(), allocator.state);
+    }
+}
+
+/// Take the string which rcl allocated with the default allocator.
+pub unsafe fn take_c_string(string: *mut c_char) -> Option<String> {
+    let result = String::from_c_char(string);
+    deallocate(string);
+    result
+}
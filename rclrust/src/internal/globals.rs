@@ -0,0 +1,80 @@
+//! Process-wide state of rclrust
+//!
+//! A component library links its own copy of rclrust, whose statics are separate from the ones
+//! of the container. The container makes the library use its state on loading it, so that
+//! both copies serialize the logging of rcutils, share the output handlers, spawn tasks on the
+//! same async runtime and so on.
+
+use std::{
+    collections::HashMap,
+    os::raw::c_void,
+    path::PathBuf,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicPtr, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use libloading::Library;
+use once_cell::sync::Lazy;
+use parking_lot::ReentrantMutex;
+
+use super::{async_api, signal_handler};
+use crate::log::output_handler;
+
+pub struct Globals {
+    /// Runtime of the process, which component libraries spawn their tasks on
+    pub runtime: async_api::Runtime,
+    /// Serializes the logging functions of rcutils, which are not thread-safe.
+    pub logger_mutex: ReentrantMutex<()>,
+    pub output_handlers: parking_lot::Mutex<Vec<Arc<output_handler::Callback>>>,
+    /// Whether output handlers are running, which is enough to detect recursive calls because
+    /// output handlers are called with `logger_mutex` locked.
+    pub in_output_handler: AtomicBool,
+    pub signal_registry: Mutex<signal_handler::Registry>,
+    /// Loaded component libraries, which are kept for the lifetime of the process because the
+    /// registered factories and the code of loaded components live in them.
+    pub component_libraries: Mutex<HashMap<PathBuf, Library>>,
+    /// Loaded type support libraries, which are kept for the lifetime of the process because
+    /// handles created from the type supports may outlive any publisher or subscription that
+    /// refers to them.
+    pub type_support_libraries: Mutex<HashMap<String, Library>>,
+}
+
+static LOCAL: Lazy<Globals> = Lazy::new(|| Globals {
+    runtime: async_api::RUNTIME,
+    logger_mutex: ReentrantMutex::new(()),
+    output_handlers: Default::default(),
+    in_output_handler: AtomicBool::new(false),
+    signal_registry: Default::default(),
+    component_libraries: Default::default(),
+    type_support_libraries: Default::default(),
+});
+
+/// State of the container if this copy of rclrust is in a component library
+static SHARED: AtomicPtr<Globals> = AtomicPtr::new(ptr::null_mut());
+
+pub fn get() -> &'static Globals {
+    let shared = SHARED.load(Ordering::Acquire);
+    if shared.is_null() {
+        &LOCAL
+    } else {
+        unsafe { &*shared }
+    }
+}
+
+/// Get the state to pass to [`share`] of a component library.
+pub fn as_ptr() -> *const c_void {
+    get() as *const Globals as *const c_void
+}
+
+/// Use the state of the container instead of the one of this copy of rclrust.
+///
+/// # Safety
+///
+/// `globals` must be given by [`as_ptr`] of rclrust built with the same version and the same
+/// compiler, and must live until the process exits.
+pub unsafe fn share(globals: *const c_void) {
+    SHARED.store(globals as *mut Globals, Ordering::Release);
+}
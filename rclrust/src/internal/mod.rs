@@ -1,3 +1,5 @@
 pub mod async_api;
 pub mod ffi;
+pub mod globals;
+pub mod signal_handler;
 pub mod worker;
@@ -0,0 +1,86 @@
+//! Shut down contexts on SIGINT and SIGTERM
+
+use std::{
+    os::raw::c_int,
+    sync::{Arc, Weak},
+    thread,
+};
+
+use anyhow::{Context as _, Result};
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+    low_level,
+};
+
+use super::globals;
+use crate::{context::ContextShutdown, log::Logger, rclrust_error};
+
+#[derive(Default)]
+pub struct Registry {
+    installed: bool,
+    contexts: Vec<Weak<ContextShutdown>>,
+}
+
+/// Shut down `context` when the process receives SIGINT or SIGTERM.
+///
+/// The signal handler is installed on the first call. Once no registered context is valid,
+/// a signal is handled by its default action, which terminates the process.
+pub fn register(context: &Arc<ContextShutdown>) -> Result<()> {
+    let mut registry = globals::get().signal_registry.lock().unwrap();
+    if !registry.installed {
+        let mut signals = Signals::new([SIGINT, SIGTERM])
+            .with_context(|| "failed to install the signal handler")?;
+        thread::Builder::new()
+            .name("rclrust-signal".into())
+            .spawn(move || signals.forever().for_each(handle_signal))
+            .with_context(|| "failed to spawn the signal handler thread")?;
+        registry.installed = true;
+    }
+    registry
+        .contexts
+        .retain(|context| context.strong_count() > 0);
+    registry.contexts.push(Arc::downgrade(context));
+    drop(registry);
+
+    Ok(())
+}
+
+fn handle_signal(signal: c_int) {
+    let contexts = globals::get()
+        .signal_registry
+        .lock()
+        .unwrap()
+        .contexts
+        .drain(..)
+        .filter_map(|context| context.upgrade())
+        .filter(|context| context.is_valid())
+        .collect::<Vec<_>>();
+
+    if contexts.is_empty() {
+        if let Err(e) = low_level::emulate_default_handler(signal) {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to handle signal {}: {}",
+                signal,
+                e
+            )
+        }
+        return;
+    }
+
+    let reason = format!(
+        "signal {}",
+        low_level::signal_name(signal).unwrap_or("unknown")
+    );
+    for context in contexts {
+        if let Err(e) = context.shutdown(reason.clone()) {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to shut down the context on {}: {}",
+                reason,
+                e
+            )
+        }
+    }
+}
@@ -1,51 +1,167 @@
-use futures::{channel::mpsc, StreamExt};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll},
+};
 
-use super::async_api::{self, JoinHandle};
-use crate::{log::Logger, rclrust_debug};
+use futures::task::AtomicWaker;
+
+use super::async_api;
+use crate::{
+    callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+    log::Logger,
+    rclrust_debug,
+};
+
+#[derive(Debug)]
+pub enum WorkerSendError {
+    /// The queue is full and the overflow policy is `Error`.
+    Full,
+    /// The worker has been terminated.
+    Disconnected,
+}
 
 #[derive(Debug)]
-pub enum WorkerMessage<T> {
-    Message(T),
-    Terminate,
+struct State<T> {
+    queue: VecDeque<T>,
+    closed: bool,
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_full: Condvar,
+    waker: AtomicWaker,
+    depth: usize,
+    overflow_policy: QueueOverflowPolicy,
+}
+
+impl<T> Shared<T> {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.not_full.notify_all();
+        self.waker.wake();
+    }
+}
+
+#[derive(Debug)]
+pub struct WorkerSender<T>(Arc<Shared<T>>);
+
+impl<T> Clone for WorkerSender<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> WorkerSender<T> {
+    /// Queue a message, following the overflow policy when the queue is full.
+    pub fn send(&self, message: T) -> Result<(), WorkerSendError> {
+        let shared = &self.0;
+        let mut state = shared.state.lock().unwrap();
+        if state.closed {
+            return Err(WorkerSendError::Disconnected);
+        }
+
+        if state.queue.len() >= shared.depth {
+            match shared.overflow_policy {
+                QueueOverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                }
+                QueueOverflowPolicy::DropNewest => return Ok(()),
+                QueueOverflowPolicy::Block => {
+                    state = shared
+                        .not_full
+                        .wait_while(state, |s| !s.closed && s.queue.len() >= shared.depth)
+                        .unwrap();
+                    if state.closed {
+                        return Err(WorkerSendError::Disconnected);
+                    }
+                }
+                QueueOverflowPolicy::Error => return Err(WorkerSendError::Full),
+            }
+        }
+
+        state.queue.push_back(message);
+        drop(state);
+        shared.waker.wake();
+
+        Ok(())
+    }
+}
+
+struct Receive<'a, T>(&'a Shared<T>);
+
+impl<'a, T> Future for Receive<'a, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = self.0;
+        let mut state = shared.state.lock().unwrap();
+        match state.queue.pop_front() {
+            Some(message) => {
+                shared.not_full.notify_one();
+                Poll::Ready(Some(message))
+            }
+            None if state.closed => Poll::Ready(None),
+            None => {
+                shared.waker.register(cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Closes the queue even when the callback panics, so that senders do not wait forever.
+struct CloseOnDrop<T>(Arc<Shared<T>>);
+
+impl<T> Drop for CloseOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
 }
 
 #[derive(Debug)]
 pub struct ReceiveWorker<T> {
-    tx: mpsc::Sender<WorkerMessage<T>>,
-    thread: Option<JoinHandle<()>>,
+    shared: Arc<Shared<T>>,
 }
 
 impl<T> ReceiveWorker<T> {
-    pub fn new<F>(callback: F) -> Self
+    pub fn new<F>(callback: F, options: &CallbackQueueOptions) -> Self
     where
         T: Send + 'static,
         F: Fn(T) + Send + 'static,
     {
-        let (tx, mut rx) = mpsc::channel(10);
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                closed: false,
+            }),
+            not_full: Condvar::new(),
+            waker: AtomicWaker::new(),
+            depth: options.depth.max(1),
+            overflow_policy: options.overflow_policy,
+        });
 
-        let thread = async_api::spawn(async move {
-            while let Some(message) = rx.next().await {
-                match message {
-                    WorkerMessage::Message(msg) => callback(msg),
-                    WorkerMessage::Terminate => break,
-                }
+        let guard = CloseOnDrop(Arc::clone(&shared));
+        async_api::spawn(async move {
+            while let Some(message) = Receive(&guard.0).await {
+                callback(message);
             }
 
             rclrust_debug!(Logger::new("rclrust"), "Channel is closed.");
         });
 
-        Self {
-            tx,
-            thread: Some(thread),
-        }
+        Self { shared }
     }
 
-    pub fn clone_tx(&self) -> mpsc::Sender<WorkerMessage<T>> {
-        self.tx.clone()
+    pub fn clone_tx(&self) -> WorkerSender<T> {
+        WorkerSender(Arc::clone(&self.shared))
     }
 
-    pub fn terminate(&mut self) {
-        let _ = self.tx.try_send(WorkerMessage::Terminate);
+    pub fn terminate(&self) {
+        self.shared.close();
     }
 }
 
@@ -54,3 +170,79 @@ impl<T> Drop for ReceiveWorker<T> {
         self.terminate();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn shared(depth: usize, overflow_policy: QueueOverflowPolicy) -> WorkerSender<usize> {
+        WorkerSender(Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                closed: false,
+            }),
+            not_full: Condvar::new(),
+            waker: AtomicWaker::new(),
+            depth,
+            overflow_policy,
+        }))
+    }
+
+    fn queued(tx: &WorkerSender<usize>) -> Vec<usize> {
+        tx.0.state.lock().unwrap().queue.iter().copied().collect()
+    }
+
+    #[test]
+    fn send_drop_oldest() {
+        let tx = shared(2, QueueOverflowPolicy::DropOldest);
+        (0..4).for_each(|i| tx.send(i).unwrap());
+        assert_eq!(queued(&tx), vec![2, 3]);
+    }
+
+    #[test]
+    fn send_drop_newest() {
+        let tx = shared(2, QueueOverflowPolicy::DropNewest);
+        (0..4).for_each(|i| tx.send(i).unwrap());
+        assert_eq!(queued(&tx), vec![0, 1]);
+    }
+
+    #[test]
+    fn send_error() {
+        let tx = shared(2, QueueOverflowPolicy::Error);
+        tx.send(0).unwrap();
+        tx.send(1).unwrap();
+        assert!(matches!(tx.send(2), Err(WorkerSendError::Full)));
+    }
+
+    #[test]
+    fn send_after_close() {
+        let tx = shared(2, QueueOverflowPolicy::Block);
+        tx.0.close();
+        assert!(matches!(tx.send(0), Err(WorkerSendError::Disconnected)));
+    }
+
+    #[tokio::test]
+    async fn worker_block() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let worker = {
+            let counter = Arc::clone(&counter);
+            ReceiveWorker::new(
+                move |_: usize| {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                    counter.fetch_add(1, Ordering::Relaxed);
+                },
+                &CallbackQueueOptions::new()
+                    .depth(1)
+                    .overflow_policy(QueueOverflowPolicy::Block),
+            )
+        };
+
+        let tx = worker.clone_tx();
+        std::thread::spawn(move || (0..5).for_each(|i| tx.send(i).unwrap()));
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 5);
+    }
+}
@@ -0,0 +1,491 @@
+//! Intra-process communication, which passes messages between publishers and subscriptions in
+//! the same context without serializing them for the middleware
+
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+
+use crate::{
+    callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+    clock::ClockType,
+    error::RclRustError,
+    internal::worker::{ReceiveWorker, WorkerSender},
+    message_info::{Gid, MessageInfo},
+    qos::{DurabilityPolicy, HistoryPolicy, QoSCompatibility, QoSProfile},
+    time::Time,
+};
+
+/// Whether a publisher or a subscription uses intra-process communication
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntraProcessSetting {
+    /// Follow [`NodeOptions::use_intra_process_comms`](crate::NodeOptions::use_intra_process_comms).
+    NodeDefault,
+    /// Use intra-process communication.
+    Enable,
+    /// Do not use intra-process communication.
+    Disable,
+}
+
+// `#[default]` on variants requires Rust 1.62.
+#[allow(clippy::derivable_impls)]
+impl Default for IntraProcessSetting {
+    fn default() -> Self {
+        Self::NodeDefault
+    }
+}
+
+impl IntraProcessSetting {
+    pub(crate) const fn is_enabled(self, node_default: bool) -> bool {
+        match self {
+            Self::NodeDefault => node_default,
+            Self::Enable => true,
+            Self::Disable => false,
+        }
+    }
+}
+
+/// Message delivered to a subscription by intra-process communication
+pub(crate) type IntraProcessMessage<T> = (Arc<T>, MessageInfo);
+
+type AnyMessage = Arc<dyn Any + Send + Sync>;
+
+/// Check if intra-process communication can be used with `qos`.
+pub(crate) fn check_qos(qos: &QoSProfile) -> Result<()> {
+    if qos.history == HistoryPolicy::KeepAll {
+        return Err(RclRustError::IntraProcessUnsupported(
+            "the history policy must be keep last".into(),
+        )
+        .into());
+    }
+    if qos.depth == 0 {
+        return Err(RclRustError::IntraProcessUnsupported(
+            "the history depth must be greater than 0".into(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Check if the middleware would connect a publisher and a subscription with these QoS.
+fn is_compatible(publisher: &QoSProfile, subscription: &QoSProfile) -> bool {
+    QoSProfile::check_compatible(publisher, subscription).compatibility != QoSCompatibility::Error
+}
+
+fn message_info(publisher_gid: Gid) -> MessageInfo {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_nanos() as i64);
+    let now = Time::from_nanosecs(now, ClockType::SystemTime);
+    MessageInfo {
+        source_timestamp: now,
+        received_timestamp: now,
+        #[cfg(feature = "rolling")]
+        publication_sequence_number: rcl_sys::RMW_MESSAGE_INFO_SEQUENCE_NUMBER_UNSUPPORTED,
+        publisher_gid,
+        from_intra_process: true,
+    }
+}
+
+struct PublisherEntry {
+    topic_name: String,
+    type_id: TypeId,
+    gid: Gid,
+    qos: QoSProfile,
+    /// Messages kept for late-joining subscriptions when the durability is transient local
+    history: VecDeque<AnyMessage>,
+}
+
+struct SubscriptionEntry {
+    topic_name: String,
+    type_id: TypeId,
+    qos: QoSProfile,
+    /// `WorkerSender<IntraProcessMessage<T>>` of the message type
+    tx: Box<dyn Any + Send + Sync>,
+}
+
+impl SubscriptionEntry {
+    fn matches(&self, publisher: &PublisherEntry) -> bool {
+        self.topic_name == publisher.topic_name
+            && self.type_id == publisher.type_id
+            && is_compatible(&publisher.qos, &self.qos)
+    }
+
+    fn send<T>(&self, message: Arc<T>, publisher_gid: Gid)
+    where
+        T: Send + Sync + 'static,
+    {
+        if let Some(tx) = self
+            .tx
+            .downcast_ref::<WorkerSender<IntraProcessMessage<T>>>()
+        {
+            // A closed queue belongs to a subscription being dropped.
+            let _ = tx.send((message, message_info(publisher_gid)));
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    publishers: HashMap<u64, PublisherEntry>,
+    subscriptions: HashMap<u64, SubscriptionEntry>,
+}
+
+impl State {
+    #[allow(clippy::missing_const_for_fn)]
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+/// Publishers and subscriptions of a context which use intra-process communication
+#[derive(Default)]
+pub(crate) struct IntraProcessManager {
+    state: Mutex<State>,
+}
+
+impl fmt::Debug for IntraProcessManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("IntraProcessManager")
+            .field("publishers", &state.publishers.len())
+            .field("subscriptions", &state.subscriptions.len())
+            .finish()
+    }
+}
+
+impl IntraProcessManager {
+    pub fn add_publisher<T>(&self, topic_name: &str, gid: Gid, qos: &QoSProfile) -> u64
+    where
+        T: 'static,
+    {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id();
+        state.publishers.insert(
+            id,
+            PublisherEntry {
+                topic_name: topic_name.into(),
+                type_id: TypeId::of::<T>(),
+                gid,
+                qos: qos.clone(),
+                history: VecDeque::new(),
+            },
+        );
+        id
+    }
+
+    pub fn remove_publisher(&self, id: u64) {
+        self.state.lock().unwrap().publishers.remove(&id);
+    }
+
+    /// Register a subscription, which receives the messages kept by transient local publishers
+    /// if it is transient local too.
+    pub fn add_subscription<T>(
+        &self,
+        topic_name: &str,
+        qos: &QoSProfile,
+        tx: WorkerSender<IntraProcessMessage<T>>,
+    ) -> u64
+    where
+        T: Send + Sync + 'static,
+    {
+        let entry = SubscriptionEntry {
+            topic_name: topic_name.into(),
+            type_id: TypeId::of::<T>(),
+            qos: qos.clone(),
+            tx: Box::new(tx),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if qos.durability == DurabilityPolicy::TransientLocal {
+            for publisher in state.publishers.values() {
+                if !entry.matches(publisher) {
+                    continue;
+                }
+                for message in &publisher.history {
+                    if let Ok(message) = Arc::clone(message).downcast::<T>() {
+                        entry.send(message, publisher.gid);
+                    }
+                }
+            }
+        }
+
+        let id = state.next_id();
+        state.subscriptions.insert(id, entry);
+        id
+    }
+
+    pub fn remove_subscription(&self, id: u64) {
+        self.state.lock().unwrap().subscriptions.remove(&id);
+    }
+
+    /// Get how many subscriptions receive messages from the publisher of `publisher_id`.
+    pub fn matched_subscription_count(&self, publisher_id: u64) -> usize {
+        let state = self.state.lock().unwrap();
+        state.publishers.get(&publisher_id).map_or(0, |publisher| {
+            state
+                .subscriptions
+                .values()
+                .filter(|subscription| subscription.matches(publisher))
+                .count()
+        })
+    }
+
+    /// Deliver a message to the matched subscriptions and return how many they are.
+    pub fn publish<T>(&self, publisher_id: u64, message: Arc<T>) -> usize
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut state = self.state.lock().unwrap();
+        let State {
+            publishers,
+            subscriptions,
+            ..
+        } = &mut *state;
+        let publisher = match publishers.get_mut(&publisher_id) {
+            Some(publisher) => publisher,
+            None => return 0,
+        };
+
+        if publisher.qos.durability == DurabilityPolicy::TransientLocal {
+            publisher
+                .history
+                .push_back(Arc::clone(&message) as AnyMessage);
+            while publisher.history.len() > publisher.qos.depth {
+                publisher.history.pop_front();
+            }
+        }
+
+        let mut count = 0;
+        for subscription in subscriptions.values() {
+            if subscription.matches(publisher) {
+                subscription.send(Arc::clone(&message), publisher.gid);
+                count += 1;
+            }
+        }
+        drop(state);
+        count
+    }
+
+    /// Check if the publisher of `gid` delivers its messages to the subscription of
+    /// `subscription_id` directly, which makes its messages via the middleware duplicates.
+    ///
+    /// Messages of types which are the same but have different `TypeId`s, such as ones built
+    /// into separate component libraries, are delivered by the middleware only.
+    pub fn delivers_to(&self, gid: &Gid, subscription_id: u64) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .subscriptions
+            .get(&subscription_id)
+            .iter()
+            .any(|subscription| {
+                state
+                    .publishers
+                    .values()
+                    .any(|publisher| publisher.gid == *gid && subscription.matches(publisher))
+            })
+    }
+}
+
+/// Registration of a publisher to the manager, which is removed on drop
+#[derive(Debug)]
+pub(crate) struct IntraProcessPublisher {
+    manager: Arc<IntraProcessManager>,
+    id: u64,
+    transient_local: bool,
+}
+
+impl IntraProcessPublisher {
+    pub fn new<T>(
+        manager: Arc<IntraProcessManager>,
+        topic_name: &str,
+        gid: Gid,
+        qos: &QoSProfile,
+    ) -> Result<Self>
+    where
+        T: 'static,
+    {
+        check_qos(qos)?;
+        let id = manager.add_publisher::<T>(topic_name, gid, qos);
+
+        Ok(Self {
+            manager,
+            id,
+            transient_local: qos.durability == DurabilityPolicy::TransientLocal,
+        })
+    }
+
+    /// Check if the messages are kept for late-joining subscriptions, which requires the
+    /// middleware to publish every message too.
+    pub const fn is_transient_local(&self) -> bool {
+        self.transient_local
+    }
+
+    pub fn matched_subscription_count(&self) -> usize {
+        self.manager.matched_subscription_count(self.id)
+    }
+
+    pub fn publish<T>(&self, message: Arc<T>) -> usize
+    where
+        T: Send + Sync + 'static,
+    {
+        self.manager.publish(self.id, message)
+    }
+}
+
+impl Drop for IntraProcessPublisher {
+    fn drop(&mut self) {
+        self.manager.remove_publisher(self.id);
+    }
+}
+
+/// Registration of a subscription to the manager with the worker which calls its callback
+///
+/// The queue of the worker holds as many messages as the history depth, dropping the oldest
+/// one like the middleware does.
+pub(crate) struct IntraProcessSubscription<T> {
+    manager: Arc<IntraProcessManager>,
+    id: u64,
+    _worker: ReceiveWorker<IntraProcessMessage<T>>,
+}
+
+impl<T> IntraProcessSubscription<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub fn new<F>(
+        manager: Arc<IntraProcessManager>,
+        topic_name: &str,
+        qos: &QoSProfile,
+        callback: F,
+    ) -> Result<Self>
+    where
+        F: Fn(IntraProcessMessage<T>) + Send + 'static,
+    {
+        check_qos(qos)?;
+        let queue_options = CallbackQueueOptions::new()
+            .depth(qos.depth)
+            .overflow_policy(QueueOverflowPolicy::DropOldest);
+        let worker = ReceiveWorker::new(callback, &queue_options);
+        let id = manager.add_subscription(topic_name, qos, worker.clone_tx());
+
+        Ok(Self {
+            manager,
+            id,
+            _worker: worker,
+        })
+    }
+
+    /// Get the manager and the ID of the registration.
+    pub fn registration(&self) -> (Arc<IntraProcessManager>, u64) {
+        (Arc::clone(&self.manager), self.id)
+    }
+}
+
+impl<T> Drop for IntraProcessSubscription<T> {
+    fn drop(&mut self) {
+        self.manager.remove_subscription(self.id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicI32, AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{callback_queue::CallbackQueueOptions, internal::worker::ReceiveWorker};
+
+    fn gid(id: u8) -> Gid {
+        Gid {
+            data: [id; rcl_sys::RMW_GID_STORAGE_SIZE],
+        }
+    }
+
+    fn worker<T, F>(callback: F) -> ReceiveWorker<IntraProcessMessage<T>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(IntraProcessMessage<T>) + Send + 'static,
+    {
+        ReceiveWorker::new(callback, &CallbackQueueOptions::new())
+    }
+
+    #[test]
+    fn check_intra_process_qos() {
+        assert!(check_qos(&QoSProfile::default()).is_ok());
+        assert!(check_qos(&QoSProfile::default().keep_all()).is_err());
+        assert!(check_qos(&QoSProfile::default().keep_last(0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn publish_to_matched_subscriptions() {
+        let manager = IntraProcessManager::default();
+        let qos = QoSProfile::default();
+        let publisher = manager.add_publisher::<i32>("/topic", gid(1), &qos);
+
+        let received = Arc::new(AtomicI32::new(0));
+        let matched = worker({
+            let received = Arc::clone(&received);
+            move |(message, info): IntraProcessMessage<i32>| {
+                assert!(info.from_intra_process);
+                assert_eq!(info.publisher_gid, gid(1));
+                received.store(*message, Ordering::Relaxed);
+            }
+        });
+        let subscription = manager.add_subscription("/topic", &qos, matched.clone_tx());
+        assert!(manager.delivers_to(&gid(1), subscription));
+        assert!(!manager.delivers_to(&gid(2), subscription));
+
+        let others = [
+            worker(|_: IntraProcessMessage<i64>| ()),
+            worker(|_: IntraProcessMessage<i64>| ()),
+        ];
+        let other_type = manager.add_subscription("/topic", &qos, others[0].clone_tx());
+        manager.add_subscription("/other", &qos, others[1].clone_tx());
+        let incompatible = worker(|_: IntraProcessMessage<i32>| ());
+        manager.add_subscription("/topic", &qos.transient_local(), incompatible.clone_tx());
+        assert!(!manager.delivers_to(&gid(1), other_type));
+
+        assert_eq!(manager.matched_subscription_count(publisher), 1);
+        assert_eq!(manager.publish(publisher, Arc::new(42)), 1);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(received.load(Ordering::Relaxed), 42);
+
+        manager.remove_subscription(subscription);
+        assert_eq!(manager.publish(publisher, Arc::new(43)), 0);
+        assert!(!manager.delivers_to(&gid(1), subscription));
+        manager.remove_publisher(publisher);
+    }
+
+    #[tokio::test]
+    async fn transient_local_history() {
+        let manager = IntraProcessManager::default();
+        let qos = QoSProfile::default().keep_last(2).transient_local();
+        let publisher = manager.add_publisher::<i32>("/topic", gid(1), &qos);
+        for i in 0..3 {
+            assert_eq!(manager.publish(publisher, Arc::new(i)), 0);
+        }
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let late_joining = worker({
+            let count = Arc::clone(&count);
+            move |(message, _): IntraProcessMessage<i32>| {
+                assert!(*message >= 1);
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        manager.add_subscription("/topic", &qos, late_joining.clone_tx());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+}
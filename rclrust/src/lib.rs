@@ -6,42 +6,58 @@
 )]
 #![allow(clippy::missing_safety_doc)]
 
+pub mod arguments;
+pub mod callback_queue;
 pub mod clock;
+pub mod component;
 pub mod context;
+pub mod duration;
 pub mod error;
 mod executor;
 mod graph;
 pub mod init_options;
 mod internal;
+mod intra_process;
 pub mod log;
+pub mod message_info;
+pub mod names;
 pub mod node;
 pub mod node_options;
 pub mod parameter;
+pub mod rate;
 pub mod time;
+pub mod time_source;
 pub mod timer;
+mod type_support;
 pub mod utility;
 mod wait_set;
 
+pub use arguments::{remove_ros_args, Arguments};
 pub use clock::{Clock, ClockType};
 pub use context::Context;
+pub use duration::Duration;
 pub use init_options::InitOptions;
+pub use intra_process::IntraProcessSetting;
 pub use log::Logger;
+pub use message_info::{MessageInfo, ServiceInfo};
 pub use node_options::NodeOptions;
 pub use parameter::{Parameter, ParameterType, ParameterValue};
+pub use rate::Rate;
 pub use time::Time;
 pub use utility::*;
 
 pub(crate) mod client;
-pub use client::Client;
+pub use client::{Client, ClientOptions};
 
 pub(crate) mod publisher;
-pub use publisher::Publisher;
+pub use publisher::{GenericPublisher, LoanedMessage, Publisher, PublisherOptions};
 
 pub mod qos;
+mod serialized_message;
 pub use qos::{DurabilityPolicy, HistoryPolicy, LivelinessPolicy, QoSProfile};
 
 pub(crate) mod service;
-pub use service::Service;
+pub use service::{Service, ServiceOptions};
 
 pub(crate) mod subscription;
-pub use subscription::Subscription;
+pub use subscription::{RawMessage, SerializedSubscription, Subscription, SubscriptionOptions};
@@ -0,0 +1,90 @@
+//! Bridge from the `log` crate
+//!
+//! Records are forwarded to the ROS logger named after their target, so the levels of the
+//! loggers, e.g. `--ros-args --log-level hyper.client:=debug`, apply to them.
+//!
+//! # Examples
+//!
+//! ```
+//! rclrust::log::log_bridge::init().unwrap();
+//! log::info!("forwarded to the ROS logger named after this module");
+//! ```
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use super::{logger_name_from_target, LogSeverity, Logger};
+
+static LOGGER: RosLogger = RosLogger;
+
+/// [`Log`] implementation which forwards records to ROS loggers
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RosLogger;
+
+impl Log for RosLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        Logger::new(&logger_name_from_target(metadata.target()))
+            .is_enable_for(severity_from_level(metadata.level()))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        Logger::new(&logger_name_from_target(record.target())).log_with_function(
+            severity_from_level(record.level()),
+            &record.args().to_string(),
+            record.module_path().unwrap_or_default(),
+            record.file().unwrap_or_default(),
+            record.line().unwrap_or_default(),
+        )
+    }
+
+    fn flush(&self) {}
+}
+
+/// Map a level of `log` to the severity of ROS, where `Trace` is `Debug`.
+pub const fn severity_from_level(level: Level) -> LogSeverity {
+    match level {
+        Level::Error => LogSeverity::Error,
+        Level::Warn => LogSeverity::Warn,
+        Level::Info => LogSeverity::Info,
+        Level::Debug | Level::Trace => LogSeverity::Debug,
+    }
+}
+
+/// Set [`RosLogger`] as the logger of `log`, leaving the filtering to ROS loggers.
+pub fn init() -> Result<(), SetLoggerError> {
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(LevelFilter::Trace);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn map_level() {
+        assert_eq!(severity_from_level(Level::Error), LogSeverity::Error);
+        assert_eq!(severity_from_level(Level::Warn), LogSeverity::Warn);
+        assert_eq!(severity_from_level(Level::Info), LogSeverity::Info);
+        assert_eq!(severity_from_level(Level::Debug), LogSeverity::Debug);
+        assert_eq!(severity_from_level(Level::Trace), LogSeverity::Debug);
+    }
+
+    #[test]
+    fn enabled_by_ros_logger_level() -> anyhow::Result<()> {
+        let metadata = Metadata::builder()
+            .level(Level::Debug)
+            .target("log_bridge::test")
+            .build();
+        let logger = Logger::new("log_bridge.test");
+
+        logger.set_level(LogSeverity::Info)?;
+        assert!(!RosLogger.enabled(&metadata));
+        logger.set_level(LogSeverity::Debug)?;
+        assert!(RosLogger.enabled(&metadata));
+
+        // rollback
+        logger.set_level(LogSeverity::Unset)?;
+
+        Ok(())
+    }
+}
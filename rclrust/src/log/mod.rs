@@ -0,0 +1,676 @@
+//! Loggers of ROS
+//!
+//! With the `log-bridge` and `tracing-bridge` features, the records of the `log` and `tracing`
+//! crates are forwarded to ROS loggers, and ROS logs can be forwarded into `tracing`.
+//! Closures can receive ROS logs by [`output_handler::add_output_handler`].
+
+use std::{
+    borrow::Cow,
+    convert::TryInto,
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int},
+    sync::atomic::{AtomicBool, AtomicI64, Ordering},
+};
+
+use anyhow::{Context, Result};
+use parking_lot::ReentrantMutex;
+pub use rcl_sys::RcutilsLogSeverity as LogSeverity;
+
+use crate::{error::ToRclRustResult, internal::globals, Clock, Duration};
+
+#[cfg(feature = "log-bridge")]
+pub mod log_bridge;
+pub mod output_handler;
+#[cfg(feature = "tracing-bridge")]
+pub mod tracing_bridge;
+
+#[no_mangle]
+pub(crate) unsafe extern "C" fn logging_output_handler(
+    location: *const rcl_sys::rcutils_log_location_t,
+    severity: c_int,
+    name: *const c_char,
+    timestamp: rcl_sys::rcutils_time_point_value_t,
+    format: *const c_char,
+    args: *mut rcl_sys::va_list,
+) {
+    let _guard = logger_mutex().lock();
+    rcl_sys::rcl_logging_multiple_output_handler(location, severity, name, timestamp, format, args);
+    output_handler::dispatch(location, severity, name, timestamp, format, args);
+}
+
+/// Format the message given to an output handler, leaving `args` available to other handlers.
+pub(crate) unsafe fn format_message(
+    format: *const c_char,
+    args: *mut rcl_sys::va_list,
+) -> Option<String> {
+    let allocator = rcl_sys::rcutils_get_default_allocator();
+    let mut array = rcl_sys::rcutils_get_zero_initialized_char_array();
+    rcl_sys::rcutils_char_array_init(&mut array, 0, &allocator)
+        .to_result()
+        .ok()?;
+
+    let message = rcl_sys::rcutils_char_array_vsprintf(&mut array, format, (*args).as_mut_ptr())
+        .to_result()
+        .ok()
+        .map(|()| CStr::from_ptr(array.buffer).to_string_lossy().into_owned());
+    let _ = rcl_sys::rcutils_char_array_fini(&mut array);
+
+    message
+}
+
+pub(crate) unsafe fn c_str_or_empty<'a>(ptr: *const c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy()
+    }
+}
+
+/// Derive a logger name from the target of the `log` and `tracing` crates, which is the module
+/// path by default, e.g. `hyper.client` from `hyper::client`.
+#[cfg(any(feature = "log-bridge", feature = "tracing-bridge"))]
+pub(crate) fn logger_name_from_target(target: &str) -> String {
+    target.replace("::", ".")
+}
+
+/// Lock which serializes the logging functions of rcutils
+pub(crate) fn logger_mutex() -> &'static ReentrantMutex<()> {
+    &globals::get().logger_mutex
+}
+
+#[derive(Debug)]
+pub struct Logger {
+    name: CString,
+}
+
+impl Logger {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: CString::new(name).unwrap(),
+        }
+    }
+
+    pub fn empty_name() -> Self {
+        Self {
+            name: CString::default(),
+        }
+    }
+
+    /// Log a message, whose function name is unknown.
+    pub fn log_common(&self, severity: LogSeverity, msg: &str, file: &str, line: u32) {
+        self.log_with_function(severity, msg, "()", file, line)
+    }
+
+    /// Log a message, where `function` is the path of the calling function.
+    ///
+    /// The logging macros, e.g. [`rclrust_info!`](crate::rclrust_info), fill the location.
+    pub fn log_with_function(
+        &self,
+        severity: LogSeverity,
+        msg: &str,
+        function: &str,
+        file: &str,
+        line: u32,
+    ) {
+        let _guard = logger_mutex().lock();
+
+        if !self.is_enable_for(severity) {
+            return;
+        }
+
+        let function = CString::new(function).unwrap();
+        let file = CString::new(file).unwrap();
+        // The message is passed as an argument not to be interpreted as a format string
+        let format = CString::new("%s").unwrap();
+        // A NUL byte would end the message early, so it is escaped as the two characters `\0`.
+        let msg = CString::new(msg.replace('\0', "\\0")).unwrap();
+
+        let logging_location = rcl_sys::rcutils_log_location_t {
+            function_name: function.as_ptr(),
+            file_name: file.as_ptr(),
+            line_number: line.try_into().unwrap(),
+        };
+
+        unsafe {
+            rcl_sys::rcutils_log(
+                &logging_location,
+                severity.into(),
+                self.get_name_ptr(),
+                format.as_ptr(),
+                msg.as_ptr(),
+            );
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.to_str().unwrap()
+    }
+
+    fn get_name_ptr(&self) -> *const c_char {
+        self.name.as_ptr()
+    }
+
+    pub fn get_child(&self, suffix: &str) -> Self {
+        if self.name.to_str().unwrap().is_empty() {
+            Self::empty_name()
+        } else {
+            Self::new(&format!(
+                "{}.{}",
+                self.name.clone().into_string().unwrap(),
+                suffix
+            ))
+        }
+    }
+
+    pub fn set_level(&self, level: LogSeverity) -> Result<()> {
+        let _guard = logger_mutex().lock();
+        unsafe {
+            rcl_sys::rcutils_logging_set_logger_level(self.get_name_ptr(), level.into())
+                .to_result()
+                .with_context(|| "rcl_sys::rcutils_logging_set_logger_level in Logger::set_level")
+        }
+    }
+
+    pub fn get_level(&self) -> Result<LogSeverity> {
+        LogSeverity::try_from_int(unsafe {
+            let _guard = logger_mutex().lock();
+            rcl_sys::rcutils_logging_get_logger_level(self.get_name_ptr())
+        })
+        .with_context(|| format!("{:?}", self.name))
+    }
+
+    pub fn get_effective_level(&self) -> Result<LogSeverity> {
+        LogSeverity::try_from_int(unsafe {
+            let _guard = logger_mutex().lock();
+            rcl_sys::rcutils_logging_get_logger_effective_level(self.get_name_ptr())
+        })
+        .with_context(|| format!("{:?}", self.name))
+    }
+
+    /// Whether the logger is enabled for `severity`, initializing logging if not yet
+    pub fn is_enable_for(&self, severity: LogSeverity) -> bool {
+        let _guard = logger_mutex().lock();
+        unsafe {
+            if !rcl_sys::g_rcutils_logging_initialized {
+                rcl_sys::rcutils_logging_initialize()
+                    .to_result()
+                    .expect("rcutils_logging_initialize() should succeed");
+            }
+
+            rcl_sys::rcutils_logging_logger_is_enabled_for(self.get_name_ptr(), severity.into())
+        }
+    }
+}
+
+/// State of a call site of the `*_once` and `*_skipfirst` logging macros
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct FirstCall(AtomicBool);
+
+impl FirstCall {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(true))
+    }
+
+    /// Return `true` only at the first call.
+    pub fn is_first(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// State of a call site of the `*_throttle` logging macros
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Throttle {
+    last_logged: AtomicI64,
+}
+
+impl Throttle {
+    const NEVER_LOGGED: i64 = i64::MIN;
+
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            last_logged: AtomicI64::new(Self::NEVER_LOGGED),
+        }
+    }
+
+    /// Return `true` if `period` has passed on `clock` since the last time it returned `true`.
+    ///
+    /// It returns `true` at the first call, and `false` if `clock` cannot get the current time.
+    pub fn should_log(&self, clock: &Clock, period: Duration) -> bool {
+        let now = match clock.now() {
+            Ok(now) => now.nanosecs,
+            Err(_) => return false,
+        };
+
+        let last_logged = self.last_logged.load(Ordering::Relaxed);
+        if last_logged != Self::NEVER_LOGGED && now < last_logged.saturating_add(period.nanosecs) {
+            return false;
+        }
+        self.last_logged
+            .compare_exchange(last_logged, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+/// Path of the function calling this macro, e.g. `my_crate::module::function`
+///
+/// Closures are reported as the function which defines them.
+#[macro_export]
+macro_rules! function_name {
+    () => {{
+        const fn f() {}
+        fn type_name_of<T>(_: T) -> &'static str {
+            ::std::any::type_name::<T>()
+        }
+        let name = type_name_of(f);
+        name[..name.len() - "::f".len()].trim_end_matches("::{{closure}}")
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rclrust_log {
+    ($severity:ident, $logger:expr, $($arg:tt)+) => {
+        $logger.log_with_function(
+            $crate::log::LogSeverity::$severity,
+            &format!($($arg)+),
+            $crate::function_name!(),
+            file!(),
+            line!(),
+        )
+    };
+}
+
+/// Log if the logger is enabled for the severity and `$cond` is `true`, where `$cond` is
+/// evaluated only if the logger is enabled like `RCUTILS_LOG_COND_NAMED`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rclrust_log_cond {
+    ($severity:ident, $logger:expr, $cond:expr, $($arg:tt)+) => {{
+        let logger = &$logger;
+        if logger.is_enable_for($crate::log::LogSeverity::$severity) && $cond {
+            $crate::__rclrust_log!($severity, logger, $($arg)+);
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! rclrust_debug {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::__rclrust_log!(Debug, $logger, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! rclrust_info {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::__rclrust_log!(Info, $logger, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! rclrust_warn {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::__rclrust_log!(Warn, $logger, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! rclrust_error {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::__rclrust_log!(Error, $logger, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! rclrust_fatal {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::__rclrust_log!(Fatal, $logger, $($arg)+)
+    };
+}
+
+/// Define the conditional logging macros of a severity, e.g. `rclrust_info_once!`.
+///
+/// `$d` is a `$` token, with which the defined macros declare their own metavariables. The
+/// macros above are not defined here, because macros exported by a macro cannot be used by
+/// paths in this crate, e.g. `crate::rclrust_error!`.
+macro_rules! define_conditional_log_macros {
+    (
+        $d:tt,
+        $severity:ident,
+        $once:ident,
+        $skipfirst:ident,
+        $throttle:ident,
+        $expression:ident,
+        $function:ident
+    ) => {
+        /// Log only at the first time at this call site.
+        #[macro_export]
+        macro_rules! $once {
+            ($d logger:expr, $d($d arg:tt)+) => {{
+                static FIRST_CALL: $crate::log::FirstCall = $crate::log::FirstCall::new();
+                $crate::__rclrust_log_cond!(
+                    $severity,
+                    $d logger,
+                    FIRST_CALL.is_first(),
+                    $d($d arg)+
+                )
+            }};
+        }
+
+        /// Log except at the first time at this call site.
+        #[macro_export]
+        macro_rules! $skipfirst {
+            ($d logger:expr, $d($d arg:tt)+) => {{
+                static FIRST_CALL: $crate::log::FirstCall = $crate::log::FirstCall::new();
+                $crate::__rclrust_log_cond!(
+                    $severity,
+                    $d logger,
+                    !FIRST_CALL.is_first(),
+                    $d($d arg)+
+                )
+            }};
+        }
+
+        /// Log at most once per `$period` ([`Duration`](crate::Duration)) on `$clock`
+        /// ([`Clock`](crate::Clock)) at this call site.
+        #[macro_export]
+        macro_rules! $throttle {
+            ($d logger:expr, $d clock:expr, $d period:expr, $d($d arg:tt)+) => {{
+                static THROTTLE: $crate::log::Throttle = $crate::log::Throttle::new();
+                $crate::__rclrust_log_cond!(
+                    $severity,
+                    $d logger,
+                    THROTTLE.should_log(&$d clock, $d period),
+                    $d($d arg)+
+                )
+            }};
+        }
+
+        /// Log only if `$expression` is `true`.
+        #[macro_export]
+        macro_rules! $expression {
+            ($d logger:expr, $d expression:expr, $d($d arg:tt)+) => {
+                $crate::__rclrust_log_cond!($severity, $d logger, $d expression, $d($d arg)+)
+            };
+        }
+
+        /// Log only if `$function` returns `true`.
+        #[macro_export]
+        macro_rules! $function {
+            ($d logger:expr, $d function:expr, $d($d arg:tt)+) => {
+                $crate::__rclrust_log_cond!($severity, $d logger, ($d function)(), $d($d arg)+)
+            };
+        }
+    };
+}
+
+define_conditional_log_macros!(
+    $,
+    Debug,
+    rclrust_debug_once,
+    rclrust_debug_skipfirst,
+    rclrust_debug_throttle,
+    rclrust_debug_expression,
+    rclrust_debug_function
+);
+define_conditional_log_macros!(
+    $,
+    Info,
+    rclrust_info_once,
+    rclrust_info_skipfirst,
+    rclrust_info_throttle,
+    rclrust_info_expression,
+    rclrust_info_function
+);
+define_conditional_log_macros!(
+    $,
+    Warn,
+    rclrust_warn_once,
+    rclrust_warn_skipfirst,
+    rclrust_warn_throttle,
+    rclrust_warn_expression,
+    rclrust_warn_function
+);
+define_conditional_log_macros!(
+    $,
+    Error,
+    rclrust_error_once,
+    rclrust_error_skipfirst,
+    rclrust_error_throttle,
+    rclrust_error_expression,
+    rclrust_error_function
+);
+define_conditional_log_macros!(
+    $,
+    Fatal,
+    rclrust_fatal_once,
+    rclrust_fatal_skipfirst,
+    rclrust_fatal_throttle,
+    rclrust_fatal_expression,
+    rclrust_fatal_function
+);
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use once_cell::sync::Lazy;
+
+    use super::*;
+    use crate::{ClockType, Time};
+
+    static TEST_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+    #[test]
+    fn log_message_with_nul() -> Result<()> {
+        let _ctx = crate::init()?;
+        let buffer = output_handler::LogBuffer::new();
+        let _handler = buffer.output_handler();
+
+        let logger = Logger::new("nul");
+        logger.log_common(LogSeverity::Info, "a\0b", file!(), line!());
+        assert!(buffer
+            .records()
+            .iter()
+            .any(|record| record.name == "nul" && record.message == "a\\0b"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn named_logger_init() -> Result<()> {
+        let logger = Logger::new("test");
+        assert_eq!(logger.get_name(), "test");
+        Ok(())
+    }
+
+    #[test]
+    fn get_child_logger() -> Result<()> {
+        let logger = Logger::new("test");
+        let child_logger = logger.get_child("child");
+        assert_eq!(child_logger.get_name(), "test.child");
+        let grandchild_logger = child_logger.get_child("child2");
+        assert_eq!(grandchild_logger.get_name(), "test.child.child2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_logger_level() -> Result<()> {
+        let _guard = TEST_MUTEX.lock().unwrap();
+
+        let logger = Logger::empty_name();
+
+        logger.set_level(LogSeverity::Debug)?;
+        assert_eq!(
+            logger.get_level()?,
+            LogSeverity::Debug,
+            "get_level() should return LogSeverity::Debug"
+        );
+        assert_eq!(
+            logger.get_effective_level()?,
+            LogSeverity::Debug,
+            "get_effective_level() should return LogSeverity::Debug"
+        );
+
+        // rollback
+        logger.set_level(LogSeverity::Info)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn named_logger_level() -> Result<()> {
+        let _guard = TEST_MUTEX.lock().unwrap();
+
+        let root_logger = Logger::empty_name();
+
+        let logger = Logger::new("test");
+        assert_eq!(logger.get_level()?, LogSeverity::Unset);
+        assert_eq!(logger.get_effective_level()?, root_logger.get_level()?);
+
+        logger.set_level(LogSeverity::Info)?;
+        assert_eq!(logger.get_level()?, LogSeverity::Info);
+        assert_eq!(logger.get_effective_level()?, LogSeverity::Info);
+
+        // rollback
+        logger.set_level(LogSeverity::Unset)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::cognitive_complexity)]
+    fn empty_logger_is_enable_for() -> Result<()> {
+        let _guard = TEST_MUTEX.lock().unwrap();
+
+        let logger = Logger::empty_name();
+
+        logger.set_level(LogSeverity::Unset)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(logger.is_enable_for(LogSeverity::Error));
+        assert!(logger.is_enable_for(LogSeverity::Warn));
+        assert!(logger.is_enable_for(LogSeverity::Info));
+        assert!(logger.is_enable_for(LogSeverity::Debug));
+
+        logger.set_level(LogSeverity::Fatal)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(!logger.is_enable_for(LogSeverity::Error));
+        assert!(!logger.is_enable_for(LogSeverity::Warn));
+        assert!(!logger.is_enable_for(LogSeverity::Info));
+        assert!(!logger.is_enable_for(LogSeverity::Debug));
+
+        logger.set_level(LogSeverity::Error)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(logger.is_enable_for(LogSeverity::Error));
+        assert!(!logger.is_enable_for(LogSeverity::Warn));
+        assert!(!logger.is_enable_for(LogSeverity::Info));
+        assert!(!logger.is_enable_for(LogSeverity::Debug));
+
+        logger.set_level(LogSeverity::Warn)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(logger.is_enable_for(LogSeverity::Error));
+        assert!(logger.is_enable_for(LogSeverity::Warn));
+        assert!(!logger.is_enable_for(LogSeverity::Info));
+        assert!(!logger.is_enable_for(LogSeverity::Debug));
+
+        logger.set_level(LogSeverity::Info)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(logger.is_enable_for(LogSeverity::Error));
+        assert!(logger.is_enable_for(LogSeverity::Warn));
+        assert!(logger.is_enable_for(LogSeverity::Info));
+        assert!(!logger.is_enable_for(LogSeverity::Debug));
+
+        logger.set_level(LogSeverity::Debug)?;
+        assert!(logger.is_enable_for(LogSeverity::Fatal));
+        assert!(logger.is_enable_for(LogSeverity::Error));
+        assert!(logger.is_enable_for(LogSeverity::Warn));
+        assert!(logger.is_enable_for(LogSeverity::Info));
+        assert!(logger.is_enable_for(LogSeverity::Debug));
+
+        // rollback
+        logger.set_level(LogSeverity::Info)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn function_name() {
+        assert_eq!(crate::function_name!(), "rclrust::log::test::function_name");
+        let in_closure = || crate::function_name!();
+        assert_eq!(in_closure(), "rclrust::log::test::function_name");
+    }
+
+    #[test]
+    fn first_call() {
+        let first_call = FirstCall::new();
+        assert!(first_call.is_first());
+        assert!(!first_call.is_first());
+        assert!(!first_call.is_first());
+    }
+
+    #[test]
+    fn throttle() -> Result<()> {
+        let clock = Clock::ros()?;
+        clock.enable_ros_time_override()?;
+        let set_time = |secs| clock.set_ros_time_override(Time::new(secs, 0, ClockType::RosTime));
+        let throttle = Throttle::new();
+        let period = Duration::new(10, 0);
+
+        set_time(100)?;
+        assert!(throttle.should_log(&clock, period));
+        set_time(105)?;
+        assert!(!throttle.should_log(&clock, period));
+        set_time(110)?;
+        assert!(throttle.should_log(&clock, period));
+        assert!(!throttle.should_log(&clock, period));
+
+        Ok(())
+    }
+
+    #[test]
+    fn conditional_macros() -> Result<()> {
+        let logger = Logger::new("test.conditional_macros");
+        let clock = Clock::steady()?;
+
+        logger.set_level(LogSeverity::Debug)?;
+        let mut count = 0;
+        for i in 0..3 {
+            rclrust_debug_once!(logger, "once {}", i);
+            rclrust_info_skipfirst!(logger, "skipfirst {}", i);
+            rclrust_warn_throttle!(logger, clock, Duration::new(1, 0), "throttle {}", i);
+            rclrust_error_expression!(logger, i % 2 == 0, "expression {}", i);
+            rclrust_fatal_function!(
+                logger,
+                || {
+                    count += 1;
+                    false
+                },
+                "function {}",
+                i
+            );
+        }
+        assert_eq!(count, 3);
+
+        // The condition is not evaluated if the logger is disabled
+        logger.set_level(LogSeverity::Fatal)?;
+        rclrust_error_function!(
+            logger,
+            || {
+                count += 1;
+                true
+            },
+            "disabled"
+        );
+        assert_eq!(count, 3);
+
+        // rollback
+        logger.set_level(LogSeverity::Unset)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+//! Output handlers of ROS logs in Rust
+//!
+//! Closures registered by [`add_output_handler`] receive every log after the outputs of rcl
+//! (the console, `/rosout` and log files), as long as the output handler of rclrust is installed
+//! by [`crate::init`].
+//!
+//! # Examples
+//!
+//! ```
+//! use rclrust::{log::output_handler::LogBuffer, rclrust_info, Logger};
+//!
+//! let _ctx = rclrust::init().unwrap();
+//! let buffer = LogBuffer::new();
+//! let _handler = buffer.output_handler();
+//!
+//! rclrust_info!(Logger::new("doc"), "hello");
+//! assert!(buffer
+//!     .records()
+//!     .iter()
+//!     .any(|record| record.message == "hello"));
+//! println!("{}", buffer.to_json_lines());
+//! ```
+
+use std::{
+    fmt::Write as _,
+    io::Write,
+    os::raw::{c_char, c_int},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::Mutex;
+
+use super::{c_str_or_empty, format_message, LogSeverity};
+use crate::{internal::globals, ClockType, Time};
+
+/// Log passed to output handlers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub severity: LogSeverity,
+    /// Name of the logger
+    pub name: String,
+    pub function: String,
+    pub file: String,
+    pub line: usize,
+    /// Time of the system clock when the log is issued
+    pub timestamp: Time,
+    /// Formatted message
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Serialize into a JSON object on a single line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{
+    ///     log::{output_handler::LogRecord, LogSeverity},
+    ///     ClockType, Time,
+    /// };
+    ///
+    /// let record = LogRecord {
+    ///     severity: LogSeverity::Info,
+    ///     name: "node".into(),
+    ///     function: "app::main".into(),
+    ///     file: "src/main.rs".into(),
+    ///     line: 10,
+    ///     timestamp: Time::from_nanosecs(100, ClockType::SystemTime),
+    ///     message: "say \"hello\"".into(),
+    /// };
+    /// assert_eq!(
+    ///     record.to_json(),
+    ///     r#"{"severity":"INFO","name":"node","function":"app::main","file":"src/main.rs","line":10,"timestamp":100,"message":"say \"hello\""}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\"severity\":");
+        push_json_str(&mut json, severity_name(self.severity));
+        json.push_str(",\"name\":");
+        push_json_str(&mut json, &self.name);
+        json.push_str(",\"function\":");
+        push_json_str(&mut json, &self.function);
+        json.push_str(",\"file\":");
+        push_json_str(&mut json, &self.file);
+        let _ = write!(
+            json,
+            ",\"line\":{},\"timestamp\":{},\"message\":",
+            self.line, self.timestamp.nanosecs
+        );
+        push_json_str(&mut json, &self.message);
+        json.push('}');
+        json
+    }
+}
+
+const fn severity_name(severity: LogSeverity) -> &'static str {
+    match severity {
+        LogSeverity::Unset => "UNSET",
+        LogSeverity::Debug => "DEBUG",
+        LogSeverity::Info => "INFO",
+        LogSeverity::Warn => "WARN",
+        LogSeverity::Error => "ERROR",
+        LogSeverity::Fatal => "FATAL",
+    }
+}
+
+fn push_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+pub(crate) type Callback = Box<dyn Fn(&LogRecord) + Send + Sync>;
+
+/// Handle of a callback registered by [`add_output_handler`]
+///
+/// The callback is no longer called after this is dropped.
+pub struct OutputHandler(Arc<Callback>);
+
+impl std::fmt::Debug for OutputHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OutputHandler")
+    }
+}
+
+impl Drop for OutputHandler {
+    fn drop(&mut self) {
+        globals::get()
+            .output_handlers
+            .lock()
+            .retain(|callback| !Arc::ptr_eq(callback, &self.0));
+    }
+}
+
+/// Register `callback` as an additional output handler.
+///
+/// Logs issued inside `callback` are not passed to output handlers in Rust again.
+///
+/// `callback` is called with the lock of logging held, so other threads wait to log until it
+/// returns, and it should not block for long. A panic in `callback` is caught and ignored, not
+/// to unwind into rcutils.
+pub fn add_output_handler<F>(callback: F) -> OutputHandler
+where
+    F: Fn(&LogRecord) + Send + Sync + 'static,
+{
+    let callback: Arc<Callback> = Arc::new(Box::new(callback));
+    globals::get().output_handlers.lock().push(callback.clone());
+    OutputHandler(callback)
+}
+
+/// Pass a log to the output handlers registered by [`add_output_handler`].
+pub(crate) unsafe fn dispatch(
+    location: *const rcl_sys::rcutils_log_location_t,
+    severity: c_int,
+    name: *const c_char,
+    timestamp: rcl_sys::rcutils_time_point_value_t,
+    format: *const c_char,
+    args: *mut rcl_sys::va_list,
+) {
+    let globals = globals::get();
+    let callbacks = globals.output_handlers.lock().clone();
+    if callbacks.is_empty() || globals.in_output_handler.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let message = match format_message(format, args) {
+        Some(message) => message,
+        None => return,
+    };
+    let (function, file, line) = location.as_ref().map_or_else(
+        || (String::new(), String::new(), 0),
+        |location| {
+            (
+                c_str_or_empty(location.function_name).into_owned(),
+                c_str_or_empty(location.file_name).into_owned(),
+                location.line_number,
+            )
+        },
+    );
+    let record = LogRecord {
+        severity: LogSeverity::try_from_int(severity).unwrap_or(LogSeverity::Unset),
+        name: c_str_or_empty(name).into_owned(),
+        function,
+        file,
+        line,
+        timestamp: Time::from_nanosecs(timestamp, ClockType::SystemTime),
+        message,
+    };
+
+    let _in_output_handler = InOutputHandler::enter(&globals.in_output_handler);
+    for callback in callbacks {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(&record)));
+    }
+}
+
+/// Guard of the flag that output handlers are running, which is reset even on a panic
+struct InOutputHandler<'a>(&'a AtomicBool);
+
+impl<'a> InOutputHandler<'a> {
+    fn enter(flag: &'a AtomicBool) -> Self {
+        flag.store(true, Ordering::Relaxed);
+        Self(flag)
+    }
+}
+
+impl Drop for InOutputHandler<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Output handler which writes logs into `writer` as JSON lines, e.g. to a local file.
+///
+/// Each log is written and flushed before the handler returns, which blocks every thread
+/// logging meanwhile because output handlers are called with the lock of logging held.
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::log::output_handler::{add_output_handler, json_lines};
+///
+/// let _ctx = rclrust::init().unwrap();
+/// // The file is removed when it is closed.
+/// let file = tempfile::tempfile().unwrap();
+/// let _handler = add_output_handler(json_lines(file));
+/// ```
+pub fn json_lines<W>(writer: W) -> impl Fn(&LogRecord) + Send + Sync + 'static
+where
+    W: Write + Send + 'static,
+{
+    let writer = Mutex::new(writer);
+    move |record| {
+        let mut writer = writer.lock();
+        let _ = writeln!(writer, "{}", record.to_json());
+        let _ = writer.flush();
+    }
+}
+
+/// In-memory buffer of logs, e.g. to check logs in tests
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<Vec<LogRecord>>>);
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an output handler which stores logs into this buffer.
+    pub fn output_handler(&self) -> OutputHandler {
+        let buffer = self.clone();
+        add_output_handler(move |record| buffer.0.lock().push(record.clone()))
+    }
+
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.0.lock().clone()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().clear();
+    }
+
+    /// Serialize the stored logs into JSON lines.
+    pub fn to_json_lines(&self) -> String {
+        self.0
+            .lock()
+            .iter()
+            .map(|record| record.to_json() + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rclrust_info, rclrust_warn, Logger};
+
+    #[test]
+    fn escape_json_str() {
+        let mut json = String::new();
+        push_json_str(&mut json, "a\"b\\c\nd\u{1}é");
+        assert_eq!(json, r#""a\"b\\c\nd\u0001é""#);
+    }
+
+    #[test]
+    fn receive_logs() -> anyhow::Result<()> {
+        let _ctx = crate::init()?;
+        let logger = Logger::new("output_handler_test");
+        let buffer = LogBuffer::new();
+        let handler = buffer.output_handler();
+
+        rclrust_info!(logger, "hello {}", "%d");
+        let line = line!() - 1;
+        let records: Vec<_> = buffer
+            .records()
+            .into_iter()
+            .filter(|record| record.name == "output_handler_test")
+            .collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].severity, LogSeverity::Info);
+        assert_eq!(records[0].message, "hello %d");
+        assert_eq!(
+            records[0].function,
+            "rclrust::log::output_handler::test::receive_logs"
+        );
+        assert_eq!(records[0].file, file!());
+        assert_eq!(records[0].line, line as usize);
+        assert!(records[0].timestamp.nanosecs > 0);
+
+        drop(handler);
+        rclrust_warn!(logger, "not received");
+        assert!(buffer
+            .records()
+            .iter()
+            .all(|record| record.message != "not received"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn catch_panic_in_handler() -> anyhow::Result<()> {
+        let _ctx = crate::init()?;
+        let logger = Logger::new("output_handler_panic_test");
+        let _panicking = add_output_handler(|record| {
+            if record.name == "output_handler_panic_test" {
+                panic!("panic in an output handler");
+            }
+        });
+        let buffer = LogBuffer::new();
+        let _handler = buffer.output_handler();
+
+        rclrust_info!(logger, "first");
+        rclrust_info!(logger, "second");
+        let messages: Vec<_> = buffer
+            .records()
+            .into_iter()
+            .filter(|record| record.name == "output_handler_panic_test")
+            .map(|record| record.message)
+            .collect();
+        assert_eq!(messages, ["first", "second"]);
+        // Other threads may be logging, whose output handlers run with the lock held.
+        let _guard = crate::log::logger_mutex().lock();
+        assert!(!globals::get().in_output_handler.load(Ordering::Relaxed));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+//! Bridge between the `tracing` crate and ROS logging
+//!
+//! [`RosLayer`] forwards events to the ROS logger named after their target, and
+//! [`init_output_handler`] forwards ROS logs into `tracing` as events of [`ROS_TARGET`].
+//!
+//! # Examples
+//!
+//! ```
+//! use rclrust::log::tracing_bridge::RosLayer;
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! let subscriber = tracing_subscriber::registry().with(RosLayer::new());
+//! tracing::subscriber::with_default(subscriber, || {
+//!     tracing::info!(
+//!         answer = 42,
+//!         "forwarded to the ROS logger named after this module"
+//!     );
+//! });
+//! ```
+
+use std::{
+    fmt::{self, Write},
+    os::raw::{c_char, c_int},
+};
+
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::{
+    field::Visit,
+    layer::{Context, Layer},
+};
+
+use super::{
+    c_str_or_empty, format_message, logger_name_from_target, logging_output_handler, LogSeverity,
+    Logger,
+};
+
+/// Target of the events which carry ROS logs
+///
+/// [`RosLayer`] ignores them, so that they do not return to ROS logging.
+pub const ROS_TARGET: &str = "ros";
+
+/// [`Layer`] which forwards events to ROS loggers
+///
+/// The message of an event is followed by its other fields as `key=value`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RosLayer;
+
+impl RosLayer {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RosLayer
+where
+    S: Subscriber,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if metadata.target() == ROS_TARGET {
+            return;
+        }
+
+        let logger = Logger::new(&logger_name_from_target(metadata.target()));
+        let severity = severity_from_level(metadata.level());
+        if !logger.is_enable_for(severity) {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        logger.log_with_function(
+            severity,
+            &visitor.finish(),
+            metadata.module_path().unwrap_or_default(),
+            metadata.file().unwrap_or_default(),
+            metadata.line().unwrap_or_default(),
+        );
+    }
+}
+
+/// Map a level of `tracing` to the severity of ROS, where `TRACE` is `Debug`.
+pub const fn severity_from_level(level: &Level) -> LogSeverity {
+    match *level {
+        Level::ERROR => LogSeverity::Error,
+        Level::WARN => LogSeverity::Warn,
+        Level::INFO => LogSeverity::Info,
+        Level::DEBUG | Level::TRACE => LogSeverity::Debug,
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Output handler of rcutils which forwards ROS logs into `tracing`, in addition to the outputs
+/// of rcl (the console, `/rosout` and log files).
+///
+/// Events have the target [`ROS_TARGET`] with the fields `logger`, `file`, `line` and
+/// `function`. The console output of rcl can be disabled by
+/// `--ros-args --disable-stdout-logs`.
+///
+/// rcutils has a single output handler for the whole process, so installing this replaces the
+/// one of any other library, e.g. another client library in the same process. It passes the
+/// logs on to the outputs of rcl and to [`output_handler`](super::output_handler) after
+/// `tracing`.
+pub unsafe extern "C" fn tracing_output_handler(
+    location: *const rcl_sys::rcutils_log_location_t,
+    severity: c_int,
+    name: *const c_char,
+    timestamp: rcl_sys::rcutils_time_point_value_t,
+    format: *const c_char,
+    args: *mut rcl_sys::va_list,
+) {
+    if let Some(message) = format_message(format, args) {
+        let name = c_str_or_empty(name);
+        let (file, line, function) = location.as_ref().map_or_else(
+            || ("".into(), 0, "".into()),
+            |location| {
+                (
+                    c_str_or_empty(location.file_name),
+                    location.line_number,
+                    c_str_or_empty(location.function_name),
+                )
+            },
+        );
+
+        macro_rules! event {
+            ($level:expr) => {
+                tracing::event!(
+                    target: ROS_TARGET,
+                    $level,
+                    logger = %name,
+                    file = %file,
+                    line,
+                    function = %function,
+                    "{}",
+                    message
+                )
+            };
+        }
+        match LogSeverity::try_from_int(severity) {
+            Some(LogSeverity::Fatal | LogSeverity::Error) => event!(Level::ERROR),
+            Some(LogSeverity::Warn) => event!(Level::WARN),
+            Some(LogSeverity::Info) => event!(Level::INFO),
+            _ => event!(Level::DEBUG),
+        }
+    }
+
+    logging_output_handler(location, severity, name, timestamp, format, args)
+}
+
+/// Forward ROS logs into `tracing` by [`tracing_output_handler`].
+///
+/// It must be called after [`crate::init`], which installs the output handler of rcl.
+///
+/// This replaces the output handler of rcutils for the whole process, not only for a context.
+/// A later [`crate::init`] which initializes logging installs the handler without `tracing`
+/// again, so this must be called after it as well.
+pub fn init_output_handler() {
+    let _guard = super::logger_mutex().lock();
+    unsafe { rcl_sys::rcutils_logging_set_output_handler(Some(tracing_output_handler)) }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::rclrust_info;
+
+    #[test]
+    fn map_level() {
+        assert_eq!(severity_from_level(&Level::ERROR), LogSeverity::Error);
+        assert_eq!(severity_from_level(&Level::WARN), LogSeverity::Warn);
+        assert_eq!(severity_from_level(&Level::INFO), LogSeverity::Info);
+        assert_eq!(severity_from_level(&Level::DEBUG), LogSeverity::Debug);
+        assert_eq!(severity_from_level(&Level::TRACE), LogSeverity::Debug);
+    }
+
+    struct CaptureLayer(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl<S: Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            if event.metadata().target() == ROS_TARGET {
+                let mut visitor = MessageVisitor::default();
+                event.record(&mut visitor);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((*event.metadata().level(), visitor.message));
+            }
+        }
+    }
+
+    #[test]
+    fn forward_ros_logs_into_tracing() -> anyhow::Result<()> {
+        let _ctx = crate::init()?;
+        init_output_handler();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(CaptureLayer(events.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            rclrust_info!(Logger::new("tracing_bridge_test"), "hello {}", 100 % 7);
+        });
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![(Level::INFO, "hello 2".to_string())]
+        );
+
+        Ok(())
+    }
+}
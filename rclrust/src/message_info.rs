@@ -0,0 +1,106 @@
+//! Metadata of received messages and service requests
+
+use crate::{clock::ClockType, time::Time};
+
+/// Global identifier of a publisher given by the middleware
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gid {
+    /// Bytes of the identifier, whose meaning depends on the middleware
+    pub data: [u8; rcl_sys::RMW_GID_STORAGE_SIZE],
+}
+
+impl From<&rcl_sys::rmw_gid_t> for Gid {
+    fn from(gid: &rcl_sys::rmw_gid_t) -> Self {
+        Self { data: gid.data }
+    }
+}
+
+/// Information of a message received by a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageInfo {
+    /// Time when the message was published, measured by the publisher's system clock
+    pub source_timestamp: Time,
+    /// Time when the message was received, measured by the subscriber's system clock
+    pub received_timestamp: Time,
+    /// Sequence number of the message given by the publisher, which is `0` if the middleware
+    /// does not support it
+    #[cfg(feature = "rolling")]
+    pub publication_sequence_number: u64,
+    /// Identifier of the publisher which sent the message
+    pub publisher_gid: Gid,
+    /// Whether the message came via intra-process communication or not
+    pub from_intra_process: bool,
+}
+
+impl From<&rcl_sys::rmw_message_info_t> for MessageInfo {
+    fn from(info: &rcl_sys::rmw_message_info_t) -> Self {
+        Self {
+            source_timestamp: Time::from_nanosecs(info.source_timestamp, ClockType::SystemTime),
+            received_timestamp: Time::from_nanosecs(info.received_timestamp, ClockType::SystemTime),
+            #[cfg(feature = "rolling")]
+            publication_sequence_number: info.publication_sequence_number,
+            publisher_gid: (&info.publisher_gid).into(),
+            from_intra_process: info.from_intra_process,
+        }
+    }
+}
+
+/// Identifier of a service request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequestId {
+    /// GUID of the writer which sent the request, which identifies the client
+    pub writer_guid: [u8; 16],
+    /// Sequence number of the request given by the client
+    pub sequence_number: i64,
+}
+
+impl From<&rcl_sys::rmw_request_id_t> for RequestId {
+    fn from(request_id: &rcl_sys::rmw_request_id_t) -> Self {
+        let mut writer_guid = [0; 16];
+        for (dst, src) in writer_guid.iter_mut().zip(request_id.writer_guid.iter()) {
+            *dst = *src as u8;
+        }
+
+        Self {
+            writer_guid,
+            sequence_number: request_id.sequence_number,
+        }
+    }
+}
+
+/// Information of a request received by a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceInfo {
+    /// Time when the request was sent, measured by the client's system clock
+    pub source_timestamp: Time,
+    /// Time when the request was received, measured by the service's system clock
+    pub received_timestamp: Time,
+    /// Identifier of the request
+    pub request_id: RequestId,
+}
+
+impl From<&rcl_sys::rmw_service_info_t> for ServiceInfo {
+    fn from(info: &rcl_sys::rmw_service_info_t) -> Self {
+        Self {
+            source_timestamp: Time::from_nanosecs(info.source_timestamp, ClockType::SystemTime),
+            received_timestamp: Time::from_nanosecs(info.received_timestamp, ClockType::SystemTime),
+            request_id: (&info.request_id).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn request_id_from_rmw() {
+        let request_id = rcl_sys::rmw_request_id_t {
+            writer_guid: [-1; 16],
+            sequence_number: 3,
+        };
+        let request_id = RequestId::from(&request_id);
+        assert_eq!(request_id.writer_guid, [255; 16]);
+        assert_eq!(request_id.sequence_number, 3);
+    }
+}
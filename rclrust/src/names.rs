@@ -0,0 +1,203 @@
+//! Validation of node names, namespaces, topic names and service names
+
+use std::{
+    ffi::CString,
+    fmt,
+    os::raw::{c_char, c_int},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    error::{RclRustError, ToRclRustResult},
+    internal::ffi::SizedFromCChar,
+};
+
+/// Kind of a name which is validated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameKind {
+    NodeName,
+    Namespace,
+    TopicName,
+    ServiceName,
+}
+
+impl fmt::Display for NameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::NodeName => "node name",
+            Self::Namespace => "namespace",
+            Self::TopicName => "topic name",
+            Self::ServiceName => "service name",
+        };
+        f.write_str(kind)
+    }
+}
+
+type ValidateFn = unsafe extern "C" fn(*const c_char, *mut c_int, *mut usize) -> c_int;
+type ResultStringFn = unsafe extern "C" fn(c_int) -> *const c_char;
+
+fn validate(
+    kind: NameKind,
+    name: &str,
+    validate: ValidateFn,
+    result_string: ResultStringFn,
+) -> Result<()> {
+    let c_name = CString::new(name).map_err(|e| RclRustError::InvalidName {
+        kind,
+        name: name.into(),
+        reason: "must not contain a null character".into(),
+        invalid_index: e.nul_position(),
+    })?;
+
+    let mut validation_result = 0;
+    let mut invalid_index = 0;
+    unsafe {
+        validate(c_name.as_ptr(), &mut validation_result, &mut invalid_index)
+            .to_result()
+            .with_context(|| format!("failed to validate {} \"{}\"", kind, name))?;
+    }
+    if validation_result == 0 {
+        return Ok(());
+    }
+
+    let reason = unsafe { String::from_c_char(result_string(validation_result)) }
+        .unwrap_or_else(|| format!("unknown validation result {}", validation_result));
+    Err(RclRustError::InvalidName {
+        kind,
+        name: name.into(),
+        reason,
+        invalid_index,
+    }
+    .into())
+}
+
+/// Validate a node name.
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::names::validate_node_name;
+///
+/// assert!(validate_node_name("my_node").is_ok());
+/// assert!(validate_node_name("my-node").is_err());
+/// ```
+pub fn validate_node_name(node_name: &str) -> Result<()> {
+    validate(
+        NameKind::NodeName,
+        node_name,
+        rcl_sys::rmw_validate_node_name,
+        rcl_sys::rmw_node_name_validation_result_string,
+    )
+}
+
+/// Validate an absolute namespace.
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::names::validate_namespace;
+///
+/// assert!(validate_namespace("/my_ns").is_ok());
+/// assert!(validate_namespace("my_ns").is_err());
+/// ```
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    validate(
+        NameKind::Namespace,
+        namespace,
+        rcl_sys::rmw_validate_namespace,
+        rcl_sys::rmw_namespace_validation_result_string,
+    )
+}
+
+/// Validate a fully qualified topic name.
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::names::validate_full_topic_name;
+///
+/// assert!(validate_full_topic_name("/ns/chatter").is_ok());
+/// assert!(validate_full_topic_name("chatter").is_err());
+/// ```
+pub fn validate_full_topic_name(topic_name: &str) -> Result<()> {
+    validate_full_name(NameKind::TopicName, topic_name)
+}
+
+/// Validate a fully qualified service name.
+pub fn validate_full_service_name(service_name: &str) -> Result<()> {
+    validate_full_name(NameKind::ServiceName, service_name)
+}
+
+fn validate_full_name(kind: NameKind, name: &str) -> Result<()> {
+    validate(
+        kind,
+        name,
+        rcl_sys::rmw_validate_full_topic_name,
+        rcl_sys::rmw_full_topic_name_validation_result_string,
+    )
+}
+
+/// Validate a topic name before expansion, which may be relative, private (`~/`) or contain
+/// substitutions (`{node}`).
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::names::validate_topic_name;
+///
+/// assert!(validate_topic_name("~/chatter").is_ok());
+/// assert!(validate_topic_name("chatter/").is_err());
+/// ```
+pub fn validate_topic_name(topic_name: &str) -> Result<()> {
+    validate_relative_name(NameKind::TopicName, topic_name)
+}
+
+/// Validate a service name before expansion, like [`validate_topic_name`].
+pub fn validate_service_name(service_name: &str) -> Result<()> {
+    validate_relative_name(NameKind::ServiceName, service_name)
+}
+
+fn validate_relative_name(kind: NameKind, name: &str) -> Result<()> {
+    validate(
+        kind,
+        name,
+        rcl_sys::rcl_validate_topic_name,
+        rcl_sys::rcl_topic_name_validation_result_string,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_name_error() {
+        let error = validate_node_name("1node").unwrap_err();
+        match error.downcast_ref::<RclRustError>() {
+            Some(RclRustError::InvalidName {
+                kind,
+                name,
+                invalid_index,
+                ..
+            }) => {
+                assert_eq!(*kind, NameKind::NodeName);
+                assert_eq!(name, "1node");
+                assert_eq!(*invalid_index, 0);
+            }
+            _ => panic!("unexpected error: {}", error),
+        }
+    }
+
+    #[test]
+    fn validate_names() {
+        assert!(validate_node_name("").is_err());
+        assert!(validate_node_name("node\0").is_err());
+        assert!(validate_namespace("/").is_ok());
+        assert!(validate_namespace("/ns//a").is_err());
+        assert!(validate_full_service_name("/add_two_ints").is_ok());
+        assert!(validate_full_topic_name("/chatter/").is_err());
+        assert!(validate_topic_name("{node}/chatter").is_ok());
+        assert!(validate_service_name("a~").is_err());
+    }
+}
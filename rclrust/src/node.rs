@@ -1,8 +1,9 @@
 use std::{
     collections::HashMap,
     ffi::CString,
+    os::raw::c_void,
+    slice,
     sync::{Arc, Mutex},
-    thread::{self, JoinHandle},
     time::Duration,
 };
 
@@ -14,28 +15,39 @@ use rclrust_msg::{
 };
 
 use crate::{
-    client::Client,
-    clock::ClockType,
+    client::{Client, ClientOptions},
+    clock::{Clock, ClockType},
     context::{Context, RclContext},
-    error::ToRclRustResult,
-    executor::{Executor, ExecutorMessage},
+    error::{RclRustError, ToRclRustResult},
+    executor::{ExecutorMessage, ExecutorThread, SharedExecutor},
     graph::{RclNamesAndTypes, RclStringArray},
     internal::ffi::*,
-    log::Logger,
+    intra_process::{self, IntraProcessManager, IntraProcessMessage, IntraProcessSetting},
+    log::{logger_mutex, Logger},
+    message_info::{MessageInfo, ServiceInfo},
+    names,
     node_options::NodeOptions,
-    parameter::{Parameter, ParameterValue, Parameters},
-    publisher::Publisher,
-    qos::QoSProfile,
+    parameter::{Parameter, ParameterType, ParameterValue, Parameters},
+    publisher::{GenericPublisher, Publisher, PublisherOptions},
+    qos::{QoSOverridingOptions, QoSProfile},
+    rate::Rate,
     rclrust_error,
-    service::Service,
-    subscription::Subscription,
-    timer::Timer,
+    service::{Service, ServiceOptions},
+    subscription::{RawMessage, SerializedSubscription, Subscription, SubscriptionOptions},
+    time::Time,
+    time_source::{TimeSource, USE_SIM_TIME_PARAMETER},
+    timer::{Timer, TimerOptions},
+    type_support,
 };
 
 #[derive(Debug)]
 pub(crate) struct RclNode {
     r#impl: Box<rcl_sys::rcl_node_t>,
     context: Arc<Mutex<RclContext>>,
+    /// Whether the `/rosout` publisher of the node is created by rclrust, which rcl does in
+    /// `rcl_node_init` until galactic.
+    #[cfg(feature = "rolling")]
+    rosout: bool,
 }
 
 unsafe impl Send for RclNode {}
@@ -50,23 +62,53 @@ impl RclNode {
         let mut node = Box::new(unsafe { rcl_sys::rcl_get_zero_initialized_node() });
         let name_c_str = CString::new(name)?;
         let namespace_c_str = CString::new(namespace.unwrap_or_default())?;
+        #[cfg(feature = "foxy")]
+        let domain_id = context.lock().unwrap().domain_id();
+        #[cfg(feature = "foxy")]
+        let options_with_domain_id = domain_id
+            .map(|domain_id| options.with_default_domain_id(domain_id))
+            .transpose()?;
+        #[cfg(feature = "foxy")]
+        let options = options_with_domain_id.as_ref().unwrap_or(options);
 
         unsafe {
+            let mut context = context.lock().unwrap();
+            // The `/rosout` publisher of the node is registered to the logging system, which is
+            // used by output handlers on other threads.
+            let _guard = logger_mutex().lock();
             rcl_sys::rcl_node_init(
                 &mut *node,
                 name_c_str.as_ptr(),
                 namespace_c_str.as_ptr(),
-                context.lock().unwrap().raw_mut(),
+                context.raw_mut(),
                 options.raw(),
             )
             .to_result()
             .with_context(|| "rcl_sys::rcl_node_init in RclNode::new")?;
         }
 
-        Ok(Self {
+        #[allow(unused_mut)]
+        let mut handle = Self {
             r#impl: node,
             context,
-        })
+            #[cfg(feature = "rolling")]
+            rosout: false,
+        };
+
+        #[cfg(feature = "rolling")]
+        if unsafe { rcl_sys::rcl_logging_rosout_enabled() } && options.raw().enable_rosout {
+            unsafe {
+                let _guard = logger_mutex().lock();
+                rcl_sys::rcl_logging_rosout_init_publisher_for_node(handle.raw_mut())
+                    .to_result()
+                    .with_context(|| {
+                        "rcl_sys::rcl_logging_rosout_init_publisher_for_node in RclNode::new"
+                    })?;
+            }
+            handle.rosout = true;
+        }
+
+        Ok(handle)
     }
 
     #[inline]
@@ -111,6 +153,24 @@ impl RclNode {
         }
     }
 
+    fn resolve_name(&self, name: &str, is_service: bool, only_expand: bool) -> Result<String> {
+        let name = CString::new(name)?;
+        let mut output = std::ptr::null_mut();
+        unsafe {
+            rcl_sys::rcl_node_resolve_name(
+                self.raw(),
+                name.as_ptr(),
+                rcl_sys::rcutils_get_default_allocator(),
+                is_service,
+                only_expand,
+                &mut output,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_node_resolve_name in RclNode::resolve_name")?;
+            Ok(take_c_string(output).unwrap())
+        }
+    }
+
     pub fn get_options(&self) -> Option<&rcl_sys::rcl_node_options_t> {
         unsafe { rcl_sys::rcl_node_get_options(self.raw()).as_ref() }
     }
@@ -175,8 +235,24 @@ impl RclNode {
 
 impl Drop for RclNode {
     fn drop(&mut self) {
+        #[cfg(feature = "rolling")]
+        if self.rosout {
+            let result = unsafe {
+                let _guard = logger_mutex().lock();
+                rcl_sys::rcl_logging_rosout_fini_publisher_for_node(&mut *self.r#impl).to_result()
+            };
+            if let Err(e) = result {
+                rclrust_error!(
+                    Logger::new("rclrust"),
+                    "Failed to clean up rosout publisher: {}",
+                    e
+                )
+            }
+        }
+
         let result = unsafe {
             let _guard = self.context.lock().unwrap();
+            let _logger_guard = logger_mutex().lock();
             rcl_sys::rcl_node_fini(&mut *self.r#impl).to_result()
         };
         if let Err(e) = result {
@@ -193,9 +269,14 @@ impl Drop for RclNode {
 pub struct Node {
     pub(crate) handle: Arc<Mutex<RclNode>>,
     pub(crate) context: Arc<Mutex<RclContext>>,
-    parameters: Parameters,
-    wait_thread: Option<JoinHandle<Result<()>>>,
-    tx: mpsc::Sender<ExecutorMessage>,
+    parameters: Arc<Parameters>,
+    clock: Arc<Clock>,
+    time_source: Arc<TimeSource>,
+    executor: Arc<ExecutorThread>,
+    pub(crate) tx: mpsc::UnboundedSender<ExecutorMessage>,
+    sub_namespace: String,
+    intra_process: Arc<IntraProcessManager>,
+    use_intra_process_comms: bool,
 }
 
 impl Node {
@@ -209,26 +290,72 @@ impl Node {
 
         let context = Arc::clone(&context.handle);
         let handle = RclNode::new(Arc::clone(&context), name, namespace, options)?;
-        let parameters = Parameters::new(Arc::clone(&context), &handle)?;
+        let (executor, tx) = ExecutorThread::spawn(Arc::clone(&context));
 
-        let (tx, rx) = mpsc::channel(10);
+        Self::from_parts(context, handle, options, Arc::new(executor), tx)
+    }
 
-        let wait_thread = {
-            let context = Arc::clone(&context);
+    /// Create a node which is spun by an existing executor instead of its own one.
+    pub(crate) fn with_executor(
+        executor: &SharedExecutor,
+        name: &str,
+        namespace: Option<&str>,
+        options: &NodeOptions,
+    ) -> Result<Self> {
+        ensure!(
+            executor.context.lock().unwrap().is_valid(),
+            "Context of the executor is not valid"
+        );
 
-            thread::spawn(move || {
-                let mut executor = Executor::new(context, rx);
-                executor.spin()
-            })
-        };
+        let context = Arc::clone(&executor.context);
+        let handle = RclNode::new(Arc::clone(&context), name, namespace, options)?;
 
-        Ok(Self {
+        Self::from_parts(
+            context,
+            handle,
+            options,
+            Arc::clone(&executor.thread),
+            executor.tx.clone(),
+        )
+    }
+
+    fn from_parts(
+        context: Arc<Mutex<RclContext>>,
+        handle: RclNode,
+        options: &NodeOptions,
+        executor: Arc<ExecutorThread>,
+        tx: mpsc::UnboundedSender<ExecutorMessage>,
+    ) -> Result<Self> {
+        let parameters = Parameters::new(Arc::clone(&context), &handle, options)?;
+        let intra_process = context.lock().unwrap().intra_process_manager();
+
+        let node = Self {
             handle: Arc::new(Mutex::new(handle)),
             context,
-            parameters,
-            wait_thread: Some(wait_thread),
+            parameters: Arc::new(parameters),
+            clock: Arc::new(Clock::ros()?),
+            time_source: Arc::new(TimeSource::new()),
+            executor,
             tx,
-        })
+            sub_namespace: String::new(),
+            intra_process,
+            use_intra_process_comms: options.get_use_intra_process_comms(),
+        };
+
+        node.time_source.attach_clock(&node.clock)?;
+        node.declare_parameter(USE_SIM_TIME_PARAMETER, &ParameterValue::bool(false))?;
+        let use_sim_time = node.get_parameter(USE_SIM_TIME_PARAMETER).unwrap();
+        node.update_use_sim_time(&use_sim_time)?;
+
+        Ok(node)
+    }
+
+    pub(crate) fn shared_executor(&self) -> SharedExecutor {
+        SharedExecutor {
+            context: Arc::clone(&self.context),
+            thread: Arc::clone(&self.executor),
+            tx: self.tx.clone(),
+        }
     }
 
     pub(crate) fn clone_handle(&self) -> Arc<Mutex<RclNode>> {
@@ -332,16 +459,151 @@ impl Node {
     }
 
     pub fn set_parameter(&self, parameter: Parameter) -> Result<()> {
-        self.parameters
-            .set_parameters_atomically(&[parameter])?
-            .to_result()
+        if parameter.name == USE_SIM_TIME_PARAMETER && parameter.get_type() != ParameterType::Bool {
+            return Err(RclRustError::ParameterInvalidValue {
+                name: parameter.name,
+                reason: "must be a boolean".into(),
+            }
+            .into());
+        }
+
+        if parameter.name != USE_SIM_TIME_PARAMETER {
+            return self
+                .parameters
+                .set_parameters_atomically(slice::from_ref(&parameter))?
+                .to_result();
+        }
+
+        // Switch the time source first so that `use_sim_time` is unchanged if it fails, and
+        // switch it back if the parameter is not set.
+        let ros_time_active = self.time_source.is_ros_time_active();
+        self.update_use_sim_time(&parameter)?;
+        let result = self
+            .parameters
+            .set_parameters_atomically(slice::from_ref(&parameter))
+            .and_then(|result| result.to_result());
+        if result.is_err() {
+            if let Err(e) = self.time_source.set_use_sim_time(self, ros_time_active) {
+                rclrust_error!(
+                    Logger::new("rclrust"),
+                    "Failed to restore the time source: {}",
+                    e
+                );
+            }
+        }
+        result
+    }
+
+    fn update_use_sim_time(&self, use_sim_time: &Parameter) -> Result<()> {
+        let use_sim_time = use_sim_time.value == ParameterValue::bool(true);
+        self.time_source.set_use_sim_time(self, use_sim_time)
+    }
+
+    /// Get the ROS clock of this node, which is shared with the timers using ROS time and
+    /// follows simulated time while `use_sim_time` is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::ClockType;
+    ///
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node("node1").unwrap();
+    /// let clock = node.get_clock();
+    /// assert_eq!(clock.clock_type(), ClockType::RosTime);
+    /// ```
+    pub fn get_clock(&self) -> Arc<Clock> {
+        Arc::clone(&self.clock)
+    }
+
+    /// Get the current time of the node clock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node("node1").unwrap();
+    /// let now = node.now().unwrap();
+    /// println!("current time: {:?}", now);
+    /// ```
+    pub fn now(&self) -> Result<Time> {
+        self.clock.now()
+    }
+
+    /// Create a [`Rate`] of the node clock, which follows simulated time while `use_sim_time`
+    /// is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node("node1").unwrap();
+    /// let mut rate = node.create_rate(10.).unwrap();
+    /// assert!(rate.sleep(&ctx).unwrap());
+    /// ```
+    pub fn create_rate(&self, frequency: f64) -> Result<Rate> {
+        Rate::from_frequency(self.get_clock(), frequency)
+    }
+
+    /// Get the time source which drives the ROS clocks of this node according to the
+    /// `use_sim_time` parameter.
+    pub fn time_source(&self) -> &TimeSource {
+        &self.time_source
+    }
+
+    /// Check if publishers and subscriptions of this node use intra-process communication by
+    /// default.
+    pub const fn use_intra_process_comms(&self) -> bool {
+        self.use_intra_process_comms
+    }
+
+    /// Get the intra-process manager of the context if `setting` enables it for this node.
+    fn intra_process_manager(
+        &self,
+        setting: IntraProcessSetting,
+    ) -> Option<Arc<IntraProcessManager>> {
+        setting
+            .is_enabled(self.use_intra_process_comms)
+            .then(|| Arc::clone(&self.intra_process))
     }
 
     pub fn create_publisher<T>(&self, topic_name: &str, qos: &QoSProfile) -> Result<Publisher<T>>
     where
-        T: MessageT,
+        T: MessageT + 'static,
+    {
+        self.create_publisher_with_options(topic_name, qos, &PublisherOptions::new())
+    }
+
+    pub fn create_publisher_with_options<T>(
+        &self,
+        topic_name: &str,
+        qos: &QoSProfile,
+        options: &PublisherOptions,
+    ) -> Result<Publisher<T>>
+    where
+        T: MessageT + 'static,
     {
-        Publisher::new(self, topic_name, qos)
+        let topic_name = &self.extend_name(topic_name);
+        let qos = self.declare_qos_parameters(
+            &options.qos_overriding_options,
+            topic_name,
+            "publisher",
+            qos,
+        )?;
+        let intra_process = self.intra_process_manager(options.use_intra_process_comms);
+        Publisher::new(self, topic_name, &qos, intra_process)
+    }
+
+    /// Create a publisher whose message type is specified by name (e.g.
+    /// `"sensor_msgs/msg/Image"`) and loaded at runtime. Messages are published as serialized
+    /// CDR bytes.
+    pub fn create_generic_publisher(
+        &self,
+        topic_name: &str,
+        type_name: &str,
+        qos: &QoSProfile,
+    ) -> Result<GenericPublisher> {
+        GenericPublisher::new(self, &self.extend_name(topic_name), type_name, qos)
     }
 
     pub fn create_subscription<T, F>(
@@ -354,18 +616,80 @@ impl Node {
         T: MessageT + 'static,
         F: Fn(Arc<T>) + Send + 'static,
     {
-        let subscription = Subscription::new(
-            self,
+        self.create_subscription_with_options(
             topic_name,
-            move |msg| callback(Arc::new(unsafe { T::from_raw(&msg) })),
+            callback,
             qos,
-        )?;
-        self.tx
-            .try_send(ExecutorMessage::Subscription(Box::new(
-                subscription.create_invoker(),
-            )))
-            .expect("try_send should succeed");
-        Ok(subscription)
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    pub fn create_subscription_with_options<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(Arc<T>) + Send + 'static,
+    {
+        self.create_subscription_with_info_and_options(
+            topic_name,
+            move |msg, _| callback(msg),
+            qos,
+            options,
+        )
+    }
+
+    /// Create a subscription whose callback also receives the [`MessageInfo`] of each
+    /// message, e.g. to measure latency or to tell publishers apart.
+    pub fn create_subscription_with_info<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(Arc<T>, MessageInfo) + Send + 'static,
+    {
+        self.create_subscription_with_info_and_options(
+            topic_name,
+            callback,
+            qos,
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    pub fn create_subscription_with_info_and_options<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(Arc<T>, MessageInfo) + Send + 'static,
+    {
+        // The callback is shared with the worker of intra-process messages.
+        let callback = Arc::new(Mutex::new(callback));
+        let intra_process_callback = {
+            let callback = Arc::clone(&callback);
+            move |(msg, info): IntraProcessMessage<T>| (callback.lock().unwrap())(msg, info)
+        };
+        self.create_subscription_inner(
+            topic_name,
+            move |msg: RawMessage<T>| {
+                (callback.lock().unwrap())(Arc::new(unsafe { T::from_raw(&msg) }), *msg.info())
+            },
+            Some(Box::new(intra_process_callback)),
+            qos,
+            options,
+            true,
+        )
     }
 
     pub fn create_raw_subscription<T, F>(
@@ -378,12 +702,236 @@ impl Node {
         T: MessageT + 'static,
         F: Fn(Arc<T::Raw>) + Send + 'static,
     {
-        let subscription = Subscription::new(self, topic_name, callback, qos)?;
-        self.tx
-            .try_send(ExecutorMessage::Subscription(Box::new(
-                subscription.create_invoker(),
-            )))
-            .expect("try_send should succeed");
+        self.create_raw_subscription_with_options(
+            topic_name,
+            callback,
+            qos,
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    /// Create a subscription which receives messages in the raw representation.
+    ///
+    /// It does not support intra-process communication, so it receives messages through the
+    /// middleware unless [`IntraProcessSetting::Enable`] is given, which is an error.
+    pub fn create_raw_subscription_with_options<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(Arc<T::Raw>) + Send + 'static,
+    {
+        self.create_raw_subscription_inner(
+            topic_name,
+            move |msg: RawMessage<T>| {
+                callback(Arc::new(
+                    msg.into_owned().expect("Message should not be loaned"),
+                ))
+            },
+            qos,
+            options,
+            false,
+        )
+    }
+
+    pub fn create_raw_message_subscription<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(RawMessage<T>) + Send + 'static,
+    {
+        self.create_raw_message_subscription_with_options(
+            topic_name,
+            callback,
+            qos,
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    /// Create a subscription which receives [`RawMessage`]s, which are loaned from the
+    /// middleware without a copy if it can.
+    ///
+    /// Like [`Node::create_raw_subscription_with_options`], it does not support intra-process
+    /// communication.
+    pub fn create_raw_message_subscription_with_options<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(RawMessage<T>) + Send + 'static,
+    {
+        self.create_raw_subscription_inner(topic_name, callback, qos, options, true)
+    }
+
+    fn create_raw_subscription_inner<T, F>(
+        &self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+        loan_messages: bool,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(RawMessage<T>) + Send + 'static,
+    {
+        if options.use_intra_process_comms == IntraProcessSetting::Enable {
+            return Err(RclRustError::IntraProcessUnsupported(
+                "raw subscriptions cannot receive intra-process messages".into(),
+            )
+            .into());
+        }
+        self.create_subscription_inner(topic_name, callback, None, qos, options, loan_messages)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn create_subscription_inner<T, F>(
+        &self,
+        topic_name: &str,
+        callback: F,
+        intra_process_callback: Option<Box<dyn Fn(IntraProcessMessage<T>) + Send>>,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+        loan_messages: bool,
+    ) -> Result<Subscription<T>>
+    where
+        T: MessageT + 'static,
+        F: Fn(RawMessage<T>) + Send + 'static,
+    {
+        let topic_name = &self.extend_name(topic_name);
+        let qos = self.declare_qos_parameters(
+            &options.qos_overriding_options,
+            topic_name,
+            "subscription",
+            qos,
+        )?;
+        let intra_process = intra_process_callback.and_then(|callback| {
+            self.intra_process_manager(options.use_intra_process_comms)
+                .map(|manager| (manager, callback))
+        });
+        if intra_process.is_some() {
+            intra_process::check_qos(&qos)?;
+        }
+
+        let mut subscription = Subscription::new(
+            self,
+            topic_name,
+            callback,
+            &qos,
+            &options.callback_queue,
+            loan_messages,
+        )?;
+        if let Some((manager, callback)) = intra_process {
+            subscription.enable_intra_process(manager, &qos, callback)?;
+        }
+        self.add_to_executor(ExecutorMessage::Subscription(Box::new(
+            subscription.create_invoker(),
+        )))?;
+        Ok(subscription)
+    }
+
+    pub fn create_serialized_subscription<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+    ) -> Result<SerializedSubscription>
+    where
+        T: MessageT,
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        self.create_serialized_subscription_with_options::<T, _>(
+            topic_name,
+            callback,
+            qos,
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    pub fn create_serialized_subscription_with_options<T, F>(
+        &mut self,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<SerializedSubscription>
+    where
+        T: MessageT,
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        self.create_serialized_subscription_with_type_support(
+            T::type_support(),
+            topic_name,
+            callback,
+            qos,
+            options,
+        )
+    }
+
+    /// Create a subscription whose message type is specified by name (e.g.
+    /// `"sensor_msgs/msg/Image"`) and loaded at runtime. Messages are received as serialized
+    /// CDR bytes.
+    pub fn create_generic_subscription<F>(
+        &mut self,
+        topic_name: &str,
+        type_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+    ) -> Result<SerializedSubscription>
+    where
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        let type_support = type_support::get_message_type_support(type_name)?;
+        self.create_serialized_subscription_with_type_support(
+            type_support,
+            topic_name,
+            callback,
+            qos,
+            &SubscriptionOptions::new(),
+        )
+    }
+
+    fn create_serialized_subscription_with_type_support<F>(
+        &self,
+        type_support: *const c_void,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &SubscriptionOptions,
+    ) -> Result<SerializedSubscription>
+    where
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        let topic_name = &self.extend_name(topic_name);
+        let qos = self.declare_qos_parameters(
+            &options.qos_overriding_options,
+            topic_name,
+            "subscription",
+            qos,
+        )?;
+        let subscription = SerializedSubscription::new(
+            self,
+            type_support,
+            topic_name,
+            callback,
+            &qos,
+            &options.callback_queue,
+        )?;
+        self.add_to_executor(ExecutorMessage::Subscription(Box::new(
+            subscription.create_invoker(),
+        )))?;
         Ok(subscription)
     }
 
@@ -396,10 +944,21 @@ impl Node {
     where
         F: Fn() + Send + 'static,
     {
-        let timer = Timer::new(self, period, clock_type, callback)?;
-        self.tx
-            .try_send(ExecutorMessage::Timer(timer.create_invoker()))
-            .expect("try_send should succeed");
+        self.create_timer_with_options(period, clock_type, callback, &TimerOptions::new())
+    }
+
+    pub fn create_timer_with_options<F>(
+        &mut self,
+        period: Duration,
+        clock_type: ClockType,
+        callback: F,
+        options: &TimerOptions,
+    ) -> Result<Arc<Timer>>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let timer = Timer::new(self, period, clock_type, callback, options)?;
+        self.add_to_executor(ExecutorMessage::Timer(timer.create_invoker()))?;
         Ok(timer)
     }
 
@@ -418,10 +977,25 @@ impl Node {
     where
         Srv: ServiceT + 'static,
     {
-        let client = Client::<Srv>::new(self, service_name, qos)?;
-        self.tx
-            .try_send(ExecutorMessage::Client(Box::new(client.create_invoker())))
-            .expect("try_send should succeed");
+        self.create_client_with_options(service_name, qos, &ClientOptions::new())
+    }
+
+    pub fn create_client_with_options<Srv>(
+        &mut self,
+        service_name: &str,
+        qos: &QoSProfile,
+        options: &ClientOptions,
+    ) -> Result<Client<Srv>>
+    where
+        Srv: ServiceT + 'static,
+    {
+        let client = Client::<Srv>::new(
+            self,
+            &self.extend_name(service_name),
+            qos,
+            &options.callback_queue,
+        )?;
+        self.add_to_executor(ExecutorMessage::Client(Box::new(client.create_invoker())))?;
         Ok(client)
     }
 
@@ -435,16 +1009,69 @@ impl Node {
         Srv: ServiceT + 'static,
         F: Fn(Srv::Request) -> Srv::Response + Send + 'static,
     {
-        let service = Service::<Srv>::new(
-            self,
+        self.create_service_with_options(service_name, callback, qos, &ServiceOptions::new())
+    }
+
+    pub fn create_service_with_options<Srv, F>(
+        &mut self,
+        service_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &ServiceOptions,
+    ) -> Result<Service<Srv>>
+    where
+        Srv: ServiceT + 'static,
+        F: Fn(Srv::Request) -> Srv::Response + Send + 'static,
+    {
+        self.create_service_inner(
             service_name,
-            move |req_raw| (callback)(unsafe { req_raw.to_rust() }),
+            move |req_raw: &<Srv::Request as MessageT>::Raw, _| {
+                (callback)(unsafe { req_raw.to_rust() })
+            },
             qos,
-        )?;
-        self.tx
-            .try_send(ExecutorMessage::Service(Box::new(service.create_invoker())))
-            .expect("try_send should succeed");
-        Ok(service)
+            options,
+        )
+    }
+
+    /// Create a service whose callback also receives the [`ServiceInfo`] of each request,
+    /// e.g. to identify the caller or to measure latency.
+    pub fn create_service_with_info<Srv, F>(
+        &mut self,
+        service_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+    ) -> Result<Service<Srv>>
+    where
+        Srv: ServiceT + 'static,
+        F: Fn(Srv::Request, ServiceInfo) -> Srv::Response + Send + 'static,
+    {
+        self.create_service_with_info_and_options(
+            service_name,
+            callback,
+            qos,
+            &ServiceOptions::new(),
+        )
+    }
+
+    pub fn create_service_with_info_and_options<Srv, F>(
+        &mut self,
+        service_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &ServiceOptions,
+    ) -> Result<Service<Srv>>
+    where
+        Srv: ServiceT + 'static,
+        F: Fn(Srv::Request, ServiceInfo) -> Srv::Response + Send + 'static,
+    {
+        self.create_service_inner(
+            service_name,
+            move |req_raw: &<Srv::Request as MessageT>::Raw, info| {
+                (callback)(unsafe { req_raw.to_rust() }, info)
+            },
+            qos,
+            options,
+        )
     }
 
     pub fn create_raw_service<Srv, F>(
@@ -457,19 +1084,201 @@ impl Node {
         Srv: ServiceT + 'static,
         F: Fn(&<Srv::Request as MessageT>::Raw) -> Srv::Response + Send + 'static,
     {
-        let service = Service::new(self, service_name, callback, qos)?;
-        self.tx
-            .try_send(ExecutorMessage::Service(Box::new(service.create_invoker())))
-            .expect("try_send should succeed");
+        self.create_raw_service_with_options(service_name, callback, qos, &ServiceOptions::new())
+    }
+
+    pub fn create_raw_service_with_options<Srv, F>(
+        &mut self,
+        service_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &ServiceOptions,
+    ) -> Result<Service<Srv>>
+    where
+        Srv: ServiceT + 'static,
+        F: Fn(&<Srv::Request as MessageT>::Raw) -> Srv::Response + Send + 'static,
+    {
+        self.create_service_inner(
+            service_name,
+            move |req_raw, _| (callback)(req_raw),
+            qos,
+            options,
+        )
+    }
+
+    fn create_service_inner<Srv, F>(
+        &self,
+        service_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        options: &ServiceOptions,
+    ) -> Result<Service<Srv>>
+    where
+        Srv: ServiceT + 'static,
+        F: Fn(&<Srv::Request as MessageT>::Raw, ServiceInfo) -> Srv::Response + Send + 'static,
+    {
+        let service = Service::new(
+            self,
+            &self.extend_name(service_name),
+            callback,
+            qos,
+            &options.callback_queue,
+        )?;
+        self.add_to_executor(ExecutorMessage::Service(Box::new(service.create_invoker())))?;
         Ok(service)
     }
 
-    pub fn wait(&mut self) {
-        if let Some(handle) = self.wait_thread.take() {
-            if let Err(e) = handle.join() {
-                rclrust_error!(Logger::new("rclrust"), "{:?}", e);
+    fn declare_qos_parameters(
+        &self,
+        options: &QoSOverridingOptions,
+        topic_name: &str,
+        entity_type: &str,
+        qos: &QoSProfile,
+    ) -> Result<QoSProfile> {
+        if options.policy_kinds.is_empty() {
+            return Ok(qos.clone());
+        }
+
+        self.parameters.declare_qos_parameters(
+            options,
+            &self.resolve_name(topic_name, false, false)?,
+            entity_type,
+            qos,
+        )
+    }
+
+    /// Resolve a topic name into a fully qualified one, expanding relative and private names and
+    /// substitutions, and applying remap rules unless `only_expand` is `true`.
+    ///
+    /// The name is validated first, so that an invalid one is reported in detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node_with_ns("node1", "ns").unwrap();
+    /// assert_eq!(
+    ///     node.resolve_topic_name("chatter", false).unwrap(),
+    ///     "/ns/chatter"
+    /// );
+    /// assert_eq!(
+    ///     node.resolve_topic_name("~/chatter", false).unwrap(),
+    ///     "/ns/node1/chatter"
+    /// );
+    /// assert!(node.resolve_topic_name("chatter/", false).is_err());
+    /// ```
+    pub fn resolve_topic_name(&self, topic_name: &str, only_expand: bool) -> Result<String> {
+        self.resolve_name(&self.extend_name(topic_name), false, only_expand)
+    }
+
+    /// Resolve a service name into a fully qualified one like [`Node::resolve_topic_name`].
+    pub fn resolve_service_name(&self, service_name: &str, only_expand: bool) -> Result<String> {
+        self.resolve_name(&self.extend_name(service_name), true, only_expand)
+    }
+
+    /// Resolve a name which is already extended with the sub-namespace.
+    pub(crate) fn resolve_name(
+        &self,
+        name: &str,
+        is_service: bool,
+        only_expand: bool,
+    ) -> Result<String> {
+        if is_service {
+            names::validate_service_name(name)?;
+        } else {
+            names::validate_topic_name(name)?;
+        }
+        let resolved = self
+            .handle
+            .lock()
+            .unwrap()
+            .resolve_name(name, is_service, only_expand)?;
+        if is_service {
+            names::validate_full_service_name(&resolved)?;
+        } else {
+            names::validate_full_topic_name(&resolved)?;
+        }
+        Ok(resolved)
+    }
+
+    /// Create a sub-node, which shares the node handle, the executor and the parameters with
+    /// this node, but puts the entities with relative names under the extended namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node_with_ns("node1", "ns").unwrap();
+    /// let sub_node = node.create_sub_node("sensors").unwrap();
+    /// assert_eq!(sub_node.namespace(), "/ns");
+    /// assert_eq!(sub_node.sub_namespace(), "sensors");
+    /// assert_eq!(sub_node.effective_namespace(), "/ns/sensors");
+    /// assert_eq!(
+    ///     sub_node.resolve_topic_name("imu", false).unwrap(),
+    ///     "/ns/sensors/imu"
+    /// );
+    /// ```
+    pub fn create_sub_node(&self, sub_namespace: &str) -> Result<Self> {
+        if sub_namespace.is_empty() {
+            return Err(
+                RclRustError::InvalidArgument("sub-namespace must not be empty".into()).into(),
+            );
+        }
+        if sub_namespace.starts_with('/') {
+            return Err(RclRustError::InvalidName {
+                kind: names::NameKind::Namespace,
+                name: sub_namespace.into(),
+                reason: "sub-namespace must be relative".into(),
+                invalid_index: 0,
             }
+            .into());
         }
+        let sub_namespace = self.extend_name(sub_namespace);
+        names::validate_namespace(&join_namespace(&self.namespace(), &sub_namespace))?;
+
+        Ok(Self {
+            handle: Arc::clone(&self.handle),
+            context: Arc::clone(&self.context),
+            parameters: Arc::clone(&self.parameters),
+            clock: Arc::clone(&self.clock),
+            time_source: Arc::clone(&self.time_source),
+            executor: Arc::clone(&self.executor),
+            tx: self.tx.clone(),
+            sub_namespace,
+            intra_process: Arc::clone(&self.intra_process),
+            use_intra_process_comms: self.use_intra_process_comms,
+        })
+    }
+
+    /// Get the sub-namespace, which is empty unless this is a sub-node.
+    pub fn sub_namespace(&self) -> &str {
+        &self.sub_namespace
+    }
+
+    /// Get the namespace extended with the sub-namespace.
+    pub fn effective_namespace(&self) -> String {
+        join_namespace(&self.namespace(), &self.sub_namespace)
+    }
+
+    /// Put a relative name under the sub-namespace.
+    fn extend_name(&self, name: &str) -> String {
+        if self.sub_namespace.is_empty() || name.starts_with('/') || name.starts_with('~') {
+            name.into()
+        } else {
+            format!("{}/{}", self.sub_namespace, name)
+        }
+    }
+
+    /// Pass an entity to the executor, which fails once the executor has stopped, e.g. after
+    /// the context is shut down.
+    pub(crate) fn add_to_executor(&self, message: ExecutorMessage) -> Result<()> {
+        self.tx.unbounded_send(message).map_err(|_| {
+            RclRustError::RuntimeError("the executor of the node has already stopped").into()
+        })
+    }
+
+    pub fn wait(&mut self) {
+        self.executor.join()
     }
 
     pub fn get_topic_names_and_types(
@@ -495,14 +1304,11 @@ impl Node {
     }
 }
 
-impl Drop for Node {
-    fn drop(&mut self) {
-        self.tx
-            .try_send(ExecutorMessage::Terminate)
-            .expect("try_send should succeed");
-        if let Some(thread) = self.wait_thread.take() {
-            thread.join().unwrap().unwrap();
-        }
+fn join_namespace(namespace: &str, sub_namespace: &str) -> String {
+    match (namespace, sub_namespace) {
+        (namespace, "") => namespace.into(),
+        ("/", sub_namespace) => format!("/{}", sub_namespace),
+        (namespace, sub_namespace) => format!("{}/{}", namespace, sub_namespace),
     }
 }
 
@@ -570,6 +1376,50 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn publish_logs_on_rosout() -> Result<()> {
+        use rclrust_msg::rcl_interfaces::msg::Log;
+
+        use crate::rclrust_info;
+
+        let ctx = crate::init()?;
+        let node = ctx.create_node("rosout_node")?;
+        let disabled_node = ctx.create_node_with_options(
+            "rosout_disabled_node",
+            &NodeOptions::new().enable_rosout(false),
+        )?;
+
+        let mut listener = ctx.create_node("rosout_listener")?;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let _sub = {
+            let received = Arc::clone(&received);
+            listener.create_subscription(
+                "/rosout",
+                move |log: Arc<Log>| {
+                    received
+                        .lock()
+                        .unwrap()
+                        .push((log.name.clone(), log.msg.clone()))
+                },
+                &QoSProfile::default(),
+            )?
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        rclrust_info!(node.logger(), "hello rosout");
+        rclrust_info!(disabled_node.logger(), "hello rosout");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let received = received.lock().unwrap();
+        assert!(received.contains(&("rosout_node".to_string(), "hello rosout".to_string())));
+        assert!(!received
+            .iter()
+            .any(|(name, _)| name == "rosout_disabled_node"));
+        drop(received);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn get_topic_names_and_types() -> Result<()> {
         use rclrust_msg::std_msgs::msg::Int32;
@@ -631,6 +1481,91 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    // `{node}` is a substitution of ROS names, not a formatting argument.
+    #[allow(clippy::literal_string_with_formatting_args)]
+    fn resolve_names() -> Result<()> {
+        let args = [
+            "prog",
+            "--ros-args",
+            "-r",
+            "chatter:=talk",
+            "-r",
+            "add:=sum",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let ctx = crate::init_with_args(args, crate::InitOptions::new()?)?;
+        let node = ctx.create_node("test_node")?;
+
+        assert_eq!(node.resolve_topic_name("chatter", false)?, "/talk");
+        assert_eq!(node.resolve_topic_name("chatter", true)?, "/chatter");
+        assert_eq!(node.resolve_service_name("add", false)?, "/sum");
+        assert_eq!(
+            node.resolve_topic_name("{node}/out", false)?,
+            "/test_node/out"
+        );
+        assert_eq!(node.resolve_topic_name("~/out", false)?, "/test_node/out");
+
+        let result = node.create_publisher::<rclrust_msg::std_msgs::msg::String>(
+            "1chatter",
+            &QoSProfile::default(),
+        );
+        assert!(matches!(
+            result.err().unwrap().downcast_ref::<RclRustError>(),
+            Some(RclRustError::InvalidName { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sub_node() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node_with_ns("test_node", "ns")?;
+        let sub_node = node.create_sub_node("sensors")?.create_sub_node("imu")?;
+        assert_eq!(sub_node.sub_namespace(), "sensors/imu");
+        assert_eq!(sub_node.effective_namespace(), "/ns/sensors/imu");
+        assert_eq!(sub_node.fully_qualified_name(), "/ns/test_node");
+
+        assert_eq!(
+            sub_node.resolve_topic_name("data", false)?,
+            "/ns/sensors/imu/data"
+        );
+        assert_eq!(sub_node.resolve_topic_name("/data", false)?, "/data");
+        assert_eq!(
+            sub_node.resolve_topic_name("~/data", false)?,
+            "/ns/test_node/data"
+        );
+
+        let publisher = sub_node.create_publisher::<rclrust_msg::std_msgs::msg::Int32>(
+            "data",
+            &QoSProfile::default(),
+        )?;
+        assert_eq!(
+            publisher.topic_name().as_deref(),
+            Some("/ns/sensors/imu/data")
+        );
+
+        sub_node.declare_parameter("param", &ParameterValue::integer(1))?;
+        assert!(node.has_parameter("param"));
+
+        assert!(matches!(
+            node.create_sub_node("")
+                .unwrap_err()
+                .downcast_ref::<RclRustError>(),
+            Some(RclRustError::InvalidArgument(_))
+        ));
+        assert!(node.create_sub_node("/absolute").is_err());
+        assert!(node.create_sub_node("1invalid").is_err());
+
+        drop(node);
+        assert!(sub_node.is_valid());
+
+        Ok(())
+    }
+
     #[test]
     fn get_node_names() -> Result<()> {
         let ctx = crate::init()?;
@@ -661,4 +1596,59 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn create_publisher_with_qos_overriding_options() -> Result<()> {
+        use rclrust_msg::std_msgs::msg::Int32;
+
+        let ctx = crate::init()?;
+        let node = ctx.create_node_with_ns("test_node", "ns")?;
+        let options = PublisherOptions::new()
+            .qos_overriding_options(QoSOverridingOptions::with_default_policies());
+        let _pub1 = node.create_publisher_with_options::<Int32>(
+            "chatter",
+            &QoSProfile::default(),
+            &options,
+        )?;
+        let _pub2 = node.create_publisher_with_options::<Int32>(
+            "~/chatter",
+            &QoSProfile::default(),
+            &options,
+        )?;
+
+        assert!(node.has_parameter("qos_overrides./ns/chatter.publisher.reliability"));
+        assert!(node.has_parameter("qos_overrides./ns/test_node/chatter.publisher.depth"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_publishers_with_qos_overriding_options_on_same_topic() -> Result<()> {
+        use rclrust_msg::std_msgs::msg::Int32;
+
+        let ctx = crate::init()?;
+        let node = ctx.create_node("test_node")?;
+        let options = PublisherOptions::new()
+            .qos_overriding_options(QoSOverridingOptions::with_default_policies());
+        let pub1 = node.create_publisher_with_options::<Int32>(
+            "chatter",
+            &QoSProfile::default(),
+            &options,
+        )?;
+        let _pub2 = node.create_publisher_with_options::<Int32>(
+            "chatter",
+            &QoSProfile::default(),
+            &options,
+        )?;
+        drop(pub1);
+        let _pub3 = node.create_publisher_with_options::<Int32>(
+            "chatter",
+            &QoSProfile::default(),
+            &options,
+        )?;
+
+        assert!(node.has_parameter("qos_overrides./chatter.publisher.reliability"));
+
+        Ok(())
+    }
 }
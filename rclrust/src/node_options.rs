@@ -1,4 +1,18 @@
-use crate::{error::ToRclRustResult, log::Logger, rclrust_error};
+use std::mem;
+
+#[cfg(feature = "foxy")]
+use anyhow::Context;
+use anyhow::Result;
+
+#[cfg(feature = "galactic+")]
+use crate::qos::QoSProfile;
+use crate::{
+    arguments::{Arguments, RclArguments},
+    error::ToRclRustResult,
+    log::Logger,
+    parameter::Parameter,
+    rclrust_error,
+};
 
 #[derive(Debug)]
 pub(crate) struct RclNodeOptions(rcl_sys::rcl_node_options_t);
@@ -10,6 +24,40 @@ impl RclNodeOptions {
     pub const fn raw(&self) -> &rcl_sys::rcl_node_options_t {
         &self.0
     }
+
+    /// Replace the node specific arguments by a copy of `arguments`.
+    ///
+    /// The previous arguments are finalized if `has_arguments` is true, since the default
+    /// options hold zero initialized ones.
+    fn set_arguments(
+        &mut self,
+        arguments: &rcl_sys::rcl_arguments_t,
+        has_arguments: bool,
+    ) -> Result<()> {
+        let arguments = RclArguments::copy_from(arguments)?;
+        let previous = mem::replace(&mut self.0.arguments, arguments.into_raw());
+        if has_arguments {
+            drop(unsafe { RclArguments::from_raw(previous) });
+        }
+        Ok(())
+    }
+
+    /// Copy the options, using `domain_id` unless the domain ID is already set.
+    #[cfg(feature = "foxy")]
+    pub fn with_default_domain_id(&self, domain_id: usize) -> Result<Self> {
+        let mut options = Self::default();
+        unsafe {
+            rcl_sys::rcl_node_options_copy(&self.0, &mut options.0)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_node_options_copy in RclNodeOptions::with_default_domain_id"
+                })?;
+        }
+        if options.0.domain_id == usize::MAX {
+            options.0.domain_id = domain_id;
+        }
+        Ok(options)
+    }
 }
 
 impl Default for RclNodeOptions {
@@ -33,6 +81,9 @@ impl Drop for RclNodeOptions {
 #[derive(Debug, Default)]
 pub struct NodeOptions {
     options: RclNodeOptions,
+    arguments: Option<Arguments>,
+    parameter_overrides: Vec<Parameter>,
+    use_intra_process_comms: bool,
 }
 
 impl NodeOptions {
@@ -40,9 +91,114 @@ impl NodeOptions {
         Default::default()
     }
 
+    /// Set the command line arguments which apply only to the node.
+    ///
+    /// ROS specific arguments must follow `--ros-args`, unlike the program name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::NodeOptions;
+    ///
+    /// let args = ["--ros-args", "-r", "__node:=renamed"];
+    /// let options = NodeOptions::new()
+    ///     .arguments(args.iter().map(|s| s.to_string()).collect())
+    ///     .unwrap();
+    ///
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node_with_options("node1", &options).unwrap();
+    /// assert_eq!(&node.name(), "renamed");
+    /// ```
+    pub fn arguments(mut self, args: Vec<String>) -> Result<Self> {
+        let arguments = Arguments::parse(args)?;
+        self.options
+            .set_arguments(arguments.raw(), self.arguments.is_some())?;
+        self.arguments = Some(arguments);
+        Ok(self)
+    }
+
+    /// Set whether the global arguments given on init also apply to the node.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn use_global_arguments(mut self, use_global_arguments: bool) -> Self {
+        self.options.0.use_global_arguments = use_global_arguments;
+        self
+    }
+
+    /// Set whether the node publishes the messages of its logger on `/rosout`.
+    ///
+    /// It has no effect when rosout is disabled for the process, e.g. by
+    /// `--ros-args --disable-rosout-logs`.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn enable_rosout(mut self, enable_rosout: bool) -> Self {
+        self.options.0.enable_rosout = enable_rosout;
+        self
+    }
+
+    /// Set the QoS of the `/rosout` publisher.
+    #[cfg(feature = "galactic+")]
+    pub fn rosout_qos(mut self, qos: &QoSProfile) -> Self {
+        self.options.0.rosout_qos = qos.into();
+        self
+    }
+
+    /// Set the parameter values which override the default ones on declaration.
+    ///
+    /// They take precedence over the overrides given by the arguments.
+    pub fn parameter_overrides(mut self, parameter_overrides: Vec<Parameter>) -> Self {
+        self.parameter_overrides = parameter_overrides;
+        self
+    }
+
+    /// Set whether publishers and subscriptions of the node use intra-process communication
+    /// by default, which is overridden by their
+    /// [`IntraProcessSetting`](crate::IntraProcessSetting).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::NodeOptions;
+    ///
+    /// let options = NodeOptions::new().use_intra_process_comms(true);
+    ///
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node_with_options("node1", &options).unwrap();
+    /// assert!(node.use_intra_process_comms());
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn use_intra_process_comms(mut self, use_intra_process_comms: bool) -> Self {
+        self.use_intra_process_comms = use_intra_process_comms;
+        self
+    }
+
     pub(crate) const fn raw(&self) -> &rcl_sys::rcl_node_options_t {
         self.options.raw()
     }
+
+    pub(crate) const fn get_arguments(&self) -> Option<&Arguments> {
+        self.arguments.as_ref()
+    }
+
+    pub(crate) fn get_parameter_overrides(&self) -> &[Parameter] {
+        &self.parameter_overrides
+    }
+
+    pub(crate) const fn get_use_intra_process_comms(&self) -> bool {
+        self.use_intra_process_comms
+    }
+
+    #[cfg(feature = "foxy")]
+    pub(crate) fn with_default_domain_id(&self, domain_id: usize) -> Result<Self> {
+        Ok(Self {
+            options: self.options.with_default_domain_id(domain_id)?,
+            arguments: self
+                .arguments
+                .as_ref()
+                .map(|arguments| Arguments::from_raw(arguments.raw(), arguments.args().to_vec()))
+                .transpose()?,
+            parameter_overrides: self.parameter_overrides.clone(),
+            use_intra_process_comms: self.use_intra_process_comms,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -53,4 +209,26 @@ mod test {
     fn test_node_options_new() {
         let _options = NodeOptions::new();
     }
+
+    #[test]
+    fn test_node_options_arguments() -> Result<()> {
+        let options = NodeOptions::new()
+            .arguments(vec!["--ros-args".into(), "-p".into(), "foo:=1".into()])?
+            .arguments(vec!["--ros-args".into(), "-p".into(), "bar:=2".into()])?
+            .use_global_arguments(false);
+
+        assert_eq!(
+            options.get_arguments().unwrap().args(),
+            ["--ros-args", "-p", "bar:=2"]
+        );
+        assert!(!options.raw().use_global_arguments);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_node_options_enable_rosout() {
+        assert!(NodeOptions::new().raw().enable_rosout);
+        assert!(!NodeOptions::new().enable_rosout(false).raw().enable_rosout);
+    }
 }
@@ -115,3 +115,13 @@ impl From<RclParameter> for Parameter {
         }
     }
 }
+
+impl From<Parameter> for RclParameter {
+    fn from(v: Parameter) -> Self {
+        let Parameter { name, value } = v;
+        Self {
+            name,
+            value: value.into(),
+        }
+    }
+}
@@ -1,14 +1,23 @@
 use std::{
     collections::HashMap,
+    convert::{TryFrom, TryInto},
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use anyhow::Result;
 
 use super::{
-    Parameter, ParameterDescriptor, ParameterType, ParameterValue, RclParams, SetParametersResult,
+    value::Variant, Parameter, ParameterDescriptor, ParameterType, ParameterValue, RclParams,
+    SetParametersResult,
+};
+use crate::{
+    context::RclContext,
+    error::RclRustError,
+    node::RclNode,
+    node_options::NodeOptions,
+    qos::{self, QoSOverridingOptions, QoSPolicyKind, QoSProfile},
 };
-use crate::{context::RclContext, error::RclRustError, node::RclNode};
 
 #[derive(Debug, Default, Clone)]
 pub struct ParameterInfo {
@@ -33,17 +42,30 @@ impl Parameters {
     pub(crate) fn new(
         context_handle: Arc<Mutex<RclContext>>,
         node_handle: &RclNode,
+        options: &NodeOptions,
     ) -> Result<Self> {
         let mut parameter_overrides = HashMap::new();
+        let node_name = node_handle.fully_qualified_name();
 
         if node_handle.use_global_arguments().unwrap() {
             if let Some(rcl_params) =
                 RclParams::new(context_handle.lock().unwrap().global_arguments())?
             {
-                parameter_overrides =
-                    rcl_params.to_parameters(&node_handle.fully_qualified_name())?;
+                parameter_overrides = rcl_params.to_parameters(&node_name)?;
+            }
+        }
+        if let Some(arguments) = options.get_arguments() {
+            if let Some(rcl_params) = RclParams::new(arguments.raw())? {
+                parameter_overrides.extend(rcl_params.to_parameters(&node_name)?);
             }
         }
+        parameter_overrides.extend(
+            options
+                .get_parameter_overrides()
+                .iter()
+                .map(|parameter| (parameter.name.clone(), parameter.value.clone())),
+        );
+
         Ok(Self {
             parameter_overrides,
             ..Default::default()
@@ -207,6 +229,52 @@ impl Parameters {
             reason: "".into(),
         })
     }
+
+    /// Declare read-only parameters for the overridable QoS policies unless they are declared
+    /// already, and return the QoS profile which their values are applied to.
+    pub fn declare_qos_parameters(
+        &self,
+        options: &QoSOverridingOptions,
+        topic_name: &str,
+        entity_type: &str,
+        qos: &QoSProfile,
+    ) -> Result<QoSProfile> {
+        let mut qos = qos.clone();
+
+        let entity = if options.id.is_empty() {
+            entity_type.to_string()
+        } else {
+            format!("{}_{}", entity_type, options.id)
+        };
+
+        for &kind in &options.policy_kinds {
+            let name = format!("qos_overrides.{}.{}.{}", topic_name, entity, kind);
+            // Another entity on the same topic, or a dropped one, may have declared it already
+            // like rclcpp.
+            if !self.has_parameter(&name) {
+                self.declare_parameter(
+                    &name,
+                    &qos_policy_to_parameter_value(kind, &qos),
+                    ParameterDescriptor {
+                        description: format!("QoS policy {} of {} on {}", kind, entity, topic_name),
+                        read_only: true,
+                        ..Default::default()
+                    },
+                    false,
+                )?;
+            }
+
+            let value = self
+                .get_parameter(&name)
+                .map(|param| param.value)
+                .unwrap_or_else(ParameterValue::not_set);
+            if let Err(reason) = apply_qos_policy_parameter_value(kind, &value, &mut qos) {
+                return Err(RclRustError::ParameterInvalidValue { name, reason }.into());
+            }
+        }
+
+        Ok(qos)
+    }
 }
 
 fn set_parameters_atomically_common(
@@ -269,6 +337,74 @@ fn declare_parameter_common(
     Ok(())
 }
 
+fn qos_policy_to_parameter_value(kind: QoSPolicyKind, qos: &QoSProfile) -> ParameterValue {
+    let nanosecs = |duration: Duration| i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+
+    match kind {
+        QoSPolicyKind::AvoidRosNamespaceConventions => {
+            ParameterValue::bool(qos.avoid_ros_namespace_conventions)
+        }
+        QoSPolicyKind::Deadline => ParameterValue::integer(nanosecs(qos.deadline)),
+        QoSPolicyKind::Durability => ParameterValue::string(qos::durability_to_str(qos.durability)),
+        QoSPolicyKind::History => ParameterValue::string(qos::history_to_str(qos.history)),
+        QoSPolicyKind::Depth => {
+            ParameterValue::integer(i64::try_from(qos.depth).unwrap_or(i64::MAX))
+        }
+        QoSPolicyKind::Lifespan => ParameterValue::integer(nanosecs(qos.lifespan)),
+        QoSPolicyKind::Liveliness => ParameterValue::string(qos::liveliness_to_str(qos.liveliness)),
+        QoSPolicyKind::LivelinessLeaseDuration => {
+            ParameterValue::integer(nanosecs(qos.liveliness_lease_duration))
+        }
+        QoSPolicyKind::Reliability => {
+            ParameterValue::string(qos::reliability_to_str(qos.reliability))
+        }
+    }
+}
+
+fn apply_qos_policy_parameter_value(
+    kind: QoSPolicyKind,
+    value: &ParameterValue,
+    qos: &mut QoSProfile,
+) -> std::result::Result<(), String> {
+    let duration = |v: i64| {
+        v.try_into()
+            .map(Duration::from_nanos)
+            .map_err(|_| format!("{} policy must not be negative", kind))
+    };
+    let unknown = |v: &str| format!("unknown {} policy: {}", kind, v);
+
+    match (kind, value.get_value()) {
+        (QoSPolicyKind::AvoidRosNamespaceConventions, Some(Variant::Bool(v))) => {
+            qos.avoid_ros_namespace_conventions = v
+        }
+        (QoSPolicyKind::Deadline, Some(Variant::Integer(v))) => qos.deadline = duration(v)?,
+        (QoSPolicyKind::Durability, Some(Variant::String(v))) => {
+            qos.durability = qos::durability_from_str(&v).ok_or_else(|| unknown(&v))?
+        }
+        (QoSPolicyKind::History, Some(Variant::String(v))) => {
+            qos.history = qos::history_from_str(&v).ok_or_else(|| unknown(&v))?
+        }
+        (QoSPolicyKind::Depth, Some(Variant::Integer(v))) => {
+            qos.depth = v
+                .try_into()
+                .map_err(|_| format!("{} policy must not be negative", kind))?
+        }
+        (QoSPolicyKind::Lifespan, Some(Variant::Integer(v))) => qos.lifespan = duration(v)?,
+        (QoSPolicyKind::Liveliness, Some(Variant::String(v))) => {
+            qos.liveliness = qos::liveliness_from_str(&v).ok_or_else(|| unknown(&v))?
+        }
+        (QoSPolicyKind::LivelinessLeaseDuration, Some(Variant::Integer(v))) => {
+            qos.liveliness_lease_duration = duration(v)?
+        }
+        (QoSPolicyKind::Reliability, Some(Variant::String(v))) => {
+            qos.reliability = qos::reliability_from_str(&v).ok_or_else(|| unknown(&v))?
+        }
+        (_, _) => return Err(format!("invalid value for {} policy: {}", kind, value)),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -350,9 +486,7 @@ mod test {
 
     #[test]
     fn check_integer_range() -> Result<()> {
-        let parameters = Parameters {
-            ..Default::default()
-        };
+        let parameters = Parameters::default();
 
         use rclrust_msg::rcl_interfaces::msg::IntegerRange;
 
@@ -429,4 +563,90 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn declare_qos_parameters() -> Result<()> {
+        let mut parameter_overrides = HashMap::new();
+        parameter_overrides.insert(
+            "qos_overrides./chatter.publisher.reliability".into(),
+            ParameterValue::string("best_effort"),
+        );
+        parameter_overrides.insert(
+            "qos_overrides./chatter.publisher.depth".into(),
+            ParameterValue::integer(3),
+        );
+        let parameters = Parameters {
+            parameter_overrides,
+            ..Default::default()
+        };
+
+        let qos = parameters.declare_qos_parameters(
+            &QoSOverridingOptions::with_default_policies(),
+            "/chatter",
+            "publisher",
+            &QoSProfile::default(),
+        )?;
+        assert_eq!(qos, QoSProfile::default().keep_last(3).best_effort());
+        assert_eq!(
+            parameters.get_parameter("qos_overrides./chatter.publisher.history"),
+            Some(Parameter::string(
+                "qos_overrides./chatter.publisher.history",
+                "keep_last"
+            ))
+        );
+
+        assert!(
+            !parameters
+                .set_parameters_atomically(&[Parameter::string(
+                    "qos_overrides./chatter.publisher.reliability",
+                    "reliable"
+                )])?
+                .successful
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn declare_qos_parameters_with_id() -> Result<()> {
+        let parameters = Parameters::default();
+
+        parameters.declare_qos_parameters(
+            &QoSOverridingOptions::new(&[QoSPolicyKind::Deadline]).id("left"),
+            "/chatter",
+            "subscription",
+            &QoSProfile::default().deadline(Duration::from_millis(5)),
+        )?;
+        assert_eq!(
+            parameters.get_parameter("qos_overrides./chatter.subscription_left.deadline"),
+            Some(Parameter::integer(
+                "qos_overrides./chatter.subscription_left.deadline",
+                5_000_000
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn declare_qos_parameters_with_invalid_override() {
+        let mut parameter_overrides = HashMap::new();
+        parameter_overrides.insert(
+            "qos_overrides./chatter.publisher.reliability".into(),
+            ParameterValue::string("fast"),
+        );
+        let parameters = Parameters {
+            parameter_overrides,
+            ..Default::default()
+        };
+
+        assert!(parameters
+            .declare_qos_parameters(
+                &QoSOverridingOptions::with_default_policies(),
+                "/chatter",
+                "publisher",
+                &QoSProfile::default(),
+            )
+            .is_err());
+    }
 }
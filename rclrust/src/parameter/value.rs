@@ -217,6 +217,12 @@ impl From<RclParameterValue> for ParameterValue {
     }
 }
 
+impl From<ParameterValue> for RclParameterValue {
+    fn from(v: ParameterValue) -> Self {
+        v.0
+    }
+}
+
 impl fmt::Display for ParameterValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.get_value() {
@@ -0,0 +1,202 @@
+use std::{
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use anyhow::Result;
+use rclrust_msg::_core::MessageT;
+
+use super::{publish_intra_process, RclPublisher};
+use crate::{intra_process::IntraProcessPublisher, log::Logger, rclrust_error};
+
+enum Storage<T>
+where
+    T: MessageT,
+{
+    Loaned(NonNull<T::Raw>),
+    Owned(T::Raw),
+}
+
+/// Message loaned from the middleware, which is finalized and returned to it when dropped
+/// unless it is published.
+struct Loan<'a, T>
+where
+    T: MessageT,
+{
+    publisher: &'a RclPublisher,
+    message: NonNull<T::Raw>,
+}
+
+impl<'a, T> Loan<'a, T>
+where
+    T: MessageT,
+{
+    fn publish(self) -> Result<()> {
+        self.publisher
+            .publish_loaned_message::<T>(self.message.as_ptr())?;
+        // The middleware owns the message after it is published. Middlewares loan only messages
+        // of fixed size, whose finalization frees nothing.
+        std::mem::forget(self);
+        Ok(())
+    }
+}
+
+impl<'a, T> Drop for Loan<'a, T>
+where
+    T: MessageT,
+{
+    fn drop(&mut self) {
+        unsafe { std::ptr::drop_in_place(self.message.as_ptr()) };
+        if let Err(e) = self
+            .publisher
+            .return_loaned_message::<T>(self.message.as_ptr())
+        {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to return a loaned message: {}",
+                e
+            )
+        }
+    }
+}
+
+/// Message borrowed from a publisher, which is published on [`LoanedMessage::publish`] or
+/// when it is dropped.
+///
+/// When the middleware can loan messages, the message lives in memory owned by the middleware
+/// and is published without a copy. Otherwise it falls back to a message allocated by rclrust.
+/// Intra-process subscriptions receive a copy converted from it.
+pub struct LoanedMessage<'a, T>
+where
+    T: MessageT + 'static,
+{
+    publisher: &'a RclPublisher,
+    intra_process: Option<&'a IntraProcessPublisher>,
+    storage: Option<Storage<T>>,
+}
+
+unsafe impl<'a, T> Send for LoanedMessage<'a, T> where T: MessageT + 'static {}
+
+impl<'a, T> LoanedMessage<'a, T>
+where
+    T: MessageT + 'static,
+{
+    pub(super) fn new(
+        publisher: &'a RclPublisher,
+        intra_process: Option<&'a IntraProcessPublisher>,
+    ) -> Result<Self> {
+        let storage = if publisher.can_loan_messages() {
+            let message = NonNull::new(publisher.borrow_loaned_message::<T>()?)
+                .expect("Loaned message should not be null");
+            unsafe { message.as_ptr().write(Default::default()) };
+            Storage::Loaned(message)
+        } else {
+            Storage::Owned(Default::default())
+        };
+
+        Ok(Self {
+            publisher,
+            intra_process,
+            storage: Some(storage),
+        })
+    }
+
+    /// Check if the message is loaned from the middleware.
+    pub const fn is_loaned(&self) -> bool {
+        matches!(self.storage, Some(Storage::Loaned(_)))
+    }
+
+    /// Publish the message.
+    pub fn publish(mut self) -> Result<()> {
+        self.publish_inner()
+    }
+
+    /// Deliver the message to the intra-process subscriptions, and check if the middleware still
+    /// has to publish it.
+    fn publish_intra_process(&self, message: &T::Raw) -> Result<bool> {
+        match self.intra_process {
+            Some(intra_process)
+                if intra_process.is_transient_local()
+                    || intra_process.matched_subscription_count() > 0 =>
+            {
+                let message = Arc::new(unsafe { T::from_raw(message) });
+                publish_intra_process(self.publisher, intra_process, message)
+            }
+            _ => Ok(true),
+        }
+    }
+
+    fn publish_inner(&mut self) -> Result<()> {
+        match self.storage.take() {
+            Some(Storage::Loaned(message)) => {
+                // Finalize and return the message even if the intra-process delivery fails or
+                // panics.
+                let loan = Loan::<T> {
+                    publisher: self.publisher,
+                    message,
+                };
+                if self.publish_intra_process(unsafe { message.as_ref() })? {
+                    loan.publish()?;
+                }
+                Ok(())
+            }
+            Some(Storage::Owned(message)) => {
+                if self.publish_intra_process(&message)? {
+                    self.publisher.publish_raw::<T>(&message)?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, T> Deref for LoanedMessage<'a, T>
+where
+    T: MessageT + 'static,
+{
+    type Target = T::Raw;
+
+    fn deref(&self) -> &Self::Target {
+        match self
+            .storage
+            .as_ref()
+            .expect("Message should not be published yet")
+        {
+            Storage::Loaned(message) => unsafe { message.as_ref() },
+            Storage::Owned(message) => message,
+        }
+    }
+}
+
+impl<'a, T> DerefMut for LoanedMessage<'a, T>
+where
+    T: MessageT + 'static,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self
+            .storage
+            .as_mut()
+            .expect("Message should not be published yet")
+        {
+            Storage::Loaned(message) => unsafe { message.as_mut() },
+            Storage::Owned(message) => message,
+        }
+    }
+}
+
+impl<'a, T> Drop for LoanedMessage<'a, T>
+where
+    T: MessageT + 'static,
+{
+    fn drop(&mut self) {
+        if let Err(e) = self.publish_inner() {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to publish a loaned message: {}",
+                e
+            )
+        }
+    }
+}
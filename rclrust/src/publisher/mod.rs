@@ -1,33 +1,119 @@
 //! Publish topics
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use anyhow::Result;
 use rclrust_msg::_core::MessageT;
 
-use crate::{node::Node, qos::QoSProfile};
+use crate::{
+    intra_process::{IntraProcessManager, IntraProcessPublisher, IntraProcessSetting},
+    node::Node,
+    qos::{QoSOverridingOptions, QoSProfile},
+    serialized_message::RclSerializedMessage,
+    type_support,
+};
 
 mod rcl_wrapper;
 use rcl_wrapper::RclPublisher;
 
+mod loaned_message;
+pub use loaned_message::LoanedMessage;
+
+/// Options for creating a publisher
+#[derive(Debug, Clone, Default)]
+pub struct PublisherOptions {
+    /// QoS policies which can be overridden by parameters.
+    pub qos_overriding_options: QoSOverridingOptions,
+    /// Whether to deliver messages to subscriptions in the same context directly
+    pub use_intra_process_comms: IntraProcessSetting,
+}
+
+impl PublisherOptions {
+    /// Construct a new `PublisherOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the QoS overriding options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{qos::QoSOverridingOptions, PublisherOptions};
+    /// #
+    /// let options = PublisherOptions::new()
+    ///     .qos_overriding_options(QoSOverridingOptions::with_default_policies());
+    /// ```
+    pub fn qos_overriding_options(self, qos_overriding_options: QoSOverridingOptions) -> Self {
+        Self {
+            qos_overriding_options,
+            ..self
+        }
+    }
+
+    /// Set whether to use intra-process communication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{IntraProcessSetting, PublisherOptions};
+    /// #
+    /// let options = PublisherOptions::new().use_intra_process_comms(IntraProcessSetting::Enable);
+    /// ```
+    pub fn use_intra_process_comms(self, use_intra_process_comms: IntraProcessSetting) -> Self {
+        Self {
+            use_intra_process_comms,
+            ..self
+        }
+    }
+}
+
+/// Deliver `message` to the intra-process subscriptions, and check if the middleware still
+/// has to publish it for the others.
+fn publish_intra_process<T>(
+    handle: &RclPublisher,
+    intra_process: &IntraProcessPublisher,
+    message: Arc<T>,
+) -> Result<bool>
+where
+    T: MessageT + 'static,
+{
+    let delivered = intra_process.publish(message);
+    Ok(intra_process.is_transient_local() || handle.subscription_count()? > delivered)
+}
+
 /// Publisher
 pub struct Publisher<T>
 where
     T: MessageT,
 {
     handle: RclPublisher,
+    intra_process: Option<IntraProcessPublisher>,
     _phantom: PhantomData<T>,
 }
 
 impl<T> Publisher<T>
 where
-    T: MessageT,
+    T: MessageT + 'static,
 {
-    pub(crate) fn new(node: &Node, topic_name: &str, qos: &QoSProfile) -> Result<Self> {
+    pub(crate) fn new(
+        node: &Node,
+        topic_name: &str,
+        qos: &QoSProfile,
+        intra_process: Option<Arc<IntraProcessManager>>,
+    ) -> Result<Self> {
+        node.resolve_name(topic_name, false, false)?;
         let handle = RclPublisher::new::<T>(node.clone_handle(), topic_name, qos)?;
+        let intra_process = intra_process
+            .map(|manager| {
+                let topic_name = handle.topic_name().expect("Publisher should be valid");
+                IntraProcessPublisher::new::<T>(manager, &topic_name, handle.gid()?, qos)
+            })
+            .transpose()?;
 
         Ok(Self {
             handle,
+            intra_process,
             _phantom: Default::default(),
         })
     }
@@ -50,7 +136,124 @@ where
     /// # }
     /// ```
     pub fn publish(&self, message: &T) -> Result<()> {
-        self.handle.publish(message)
+        match self.intra_process {
+            Some(ref intra_process)
+                if intra_process.is_transient_local()
+                    || intra_process.matched_subscription_count() > 0 =>
+            {
+                self.publish_shared(Arc::new(message.clone()))
+            }
+            _ => self.handle.publish(message),
+        }
+    }
+
+    /// Publish a shared message, which intra-process subscriptions receive without a copy.
+    ///
+    /// The message is also published to the middleware unless all the subscriptions receive
+    /// it by intra-process communication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// #
+    /// # use anyhow::Result;
+    /// # use rclrust::{qos::QoSProfile, IntraProcessSetting, PublisherOptions};
+    /// use rclrust_msg::std_msgs::msg::Int32;
+    ///
+    /// # fn main() -> Result<()> {
+    /// # let ctx = rclrust::init()?;
+    /// # let node = ctx.create_node("node")?;
+    /// let options = PublisherOptions::new().use_intra_process_comms(IntraProcessSetting::Enable);
+    /// let publisher =
+    ///     node.create_publisher_with_options::<Int32>("message", &QoSProfile::default(), &options)?;
+    /// publisher.publish_shared(Arc::new(Int32 { data: 42 }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn publish_shared(&self, message: Arc<T>) -> Result<()> {
+        match self.intra_process {
+            Some(ref intra_process) => {
+                if publish_intra_process(&self.handle, intra_process, Arc::clone(&message))? {
+                    self.handle.publish(&*message)?;
+                }
+                Ok(())
+            }
+            None => self.handle.publish(&*message),
+        }
+    }
+
+    /// Check if this publisher uses intra-process communication.
+    pub const fn is_intra_process_enabled(&self) -> bool {
+        self.intra_process.is_some()
+    }
+
+    /// Borrow a message to be published, which avoids a copy when the middleware can loan
+    /// messages. The message is published by [`LoanedMessage::publish`] or when it is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rclrust::qos::QoSProfile;
+    /// use rclrust_msg::std_msgs::msg::Int32;
+    ///
+    /// # fn main() -> Result<()> {
+    /// # let ctx = rclrust::init()?;
+    /// # let node = ctx.create_node("node")?;
+    /// let publisher = node.create_publisher::<Int32>("message", &QoSProfile::default())?;
+    /// let mut message = publisher.borrow_loaned_message()?;
+    /// message.data = 42;
+    /// message.publish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn borrow_loaned_message(&self) -> Result<LoanedMessage<'_, T>> {
+        LoanedMessage::new(&self.handle, self.intra_process.as_ref())
+    }
+
+    /// Check if the middleware can loan messages to this publisher.
+    pub fn can_loan_messages(&self) -> bool {
+        self.handle.can_loan_messages()
+    }
+
+    /// Publish a message which is already serialized in CDR format.
+    ///
+    /// The bytes are passed to the middleware as is, so they must be a valid serialization
+    /// of `T`. When this publisher uses intra-process communication, they are deserialized
+    /// for the intra-process subscriptions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rclrust::qos::QoSProfile;
+    /// use rclrust_msg::std_msgs::msg::Int32;
+    ///
+    /// # fn main() -> Result<()> {
+    /// # let ctx = rclrust::init()?;
+    /// # let node = ctx.create_node("node")?;
+    /// let publisher = node.create_publisher::<Int32>("message", &QoSProfile::default())?;
+    /// // CDR little endian encapsulation header followed by `data: 42`
+    /// publisher.publish_serialized(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn publish_serialized(&self, message: &[u8]) -> Result<()> {
+        let message = RclSerializedMessage::from_bytes(message)?;
+        match self.intra_process {
+            Some(ref intra_process)
+                if intra_process.is_transient_local()
+                    || intra_process.matched_subscription_count() > 0 =>
+            {
+                let deserialized = Arc::new(message.deserialize::<T>()?);
+                if publish_intra_process(&self.handle, intra_process, deserialized)? {
+                    self.handle.publish_serialized(&message)?;
+                }
+                Ok(())
+            }
+            _ => self.handle.publish_serialized(&message),
+        }
     }
 
     /// Get the topic name which this publisher publishes on.
@@ -138,6 +341,84 @@ where
     }
 }
 
+/// Publisher whose message type is specified by name at runtime
+///
+/// Messages are published as serialized CDR bytes.
+pub struct GenericPublisher {
+    handle: RclPublisher,
+    type_name: String,
+}
+
+impl GenericPublisher {
+    pub(crate) fn new(
+        node: &Node,
+        topic_name: &str,
+        type_name: &str,
+        qos: &QoSProfile,
+    ) -> Result<Self> {
+        node.resolve_name(topic_name, false, false)?;
+        let type_support = type_support::get_message_type_support(type_name)?;
+        let handle = RclPublisher::new_with_type_support(
+            node.clone_handle(),
+            type_support,
+            topic_name,
+            qos,
+        )?;
+
+        Ok(Self {
+            handle,
+            type_name: type_name.to_string(),
+        })
+    }
+
+    /// Publish a message serialized in CDR format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rclrust::qos::QoSProfile;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let ctx = rclrust::init()?;
+    /// # let node = ctx.create_node("node")?;
+    /// let publisher =
+    ///     node.create_generic_publisher("message", "std_msgs/msg/Int32", &QoSProfile::default())?;
+    /// publisher.publish(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn publish(&self, message: &[u8]) -> Result<()> {
+        let message = RclSerializedMessage::from_bytes(message)?;
+        self.handle.publish_serialized(&message)
+    }
+
+    /// Get the type name of messages which this publisher publishes.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Get the topic name which this publisher publishes on.
+    pub fn topic_name(&self) -> Option<String> {
+        self.handle.topic_name()
+    }
+
+    /// Check if this publisher is valid or not. Normally, a return value should be `true`.
+    pub fn is_valid(&self) -> bool {
+        self.handle.is_valid()
+    }
+
+    /// Get how many subscriber are subscribing the topic which this publisher publishes on.
+    pub fn subscription_count(&self) -> Result<usize> {
+        self.handle.subscription_count()
+    }
+
+    /// Get the actual QoS settings, after the defaults have been determined.
+    pub fn actual_qos(&self) -> Option<QoSProfile> {
+        self.handle.actual_qos()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::time::Duration;
@@ -210,4 +491,21 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn generic_publisher() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node(&random_name())?;
+        let publisher =
+            node.create_generic_publisher("message", "std_msgs/msg/Int32", &QoSProfile::default())?;
+        assert_eq!(publisher.type_name(), "std_msgs/msg/Int32");
+        assert_eq!(publisher.topic_name().unwrap(), "/message");
+        assert!(publisher.is_valid());
+
+        assert!(node
+            .create_generic_publisher("message", "std_msgs/msg/NotExist", &QoSProfile::default())
+            .is_err());
+
+        Ok(())
+    }
 }
@@ -6,21 +6,21 @@
 //! - [x] `rcl_publisher_init`
 //! - [x] `rcl_publisher_fini`
 //! - [x] `rcl_publisher_get_default_options`
-//! - [ ] `rcl_borrow_loaned_message`
-//! - [ ] `rcl_return_loaned_message_from_publisher`
+//! - [x] `rcl_borrow_loaned_message`
+//! - [x] `rcl_return_loaned_message_from_publisher`
 //! - [x] `rcl_publish`
-//! - [ ] `rcl_publish_serialized_message`
-//! - [ ] `rcl_publish_loaned_message`
+//! - [x] `rcl_publish_serialized_message`
+//! - [x] `rcl_publish_loaned_message`
 //! - [ ] `rcl_publisher_assert_liveliness`
 //! - [x] `rcl_publisher_get_topic_name`
 //! - [ ] `rcl_publisher_get_options`
-//! - [ ] `rcl_publisher_get_rmw_handle`
+//! - [x] `rcl_publisher_get_rmw_handle`
 //! - [ ] `rcl_publisher_get_context`
 //! - [x] `rcl_publisher_is_valid`
 //! - [ ] `rcl_publisher_is_valid_except_context`
 //! - [x] `rcl_publisher_get_subscription_count`
 //! - [x] `rcl_publisher_get_actual_qos`
-//! - [ ] `rcl_publisher_can_loan_messages`
+//! - [x] `rcl_publisher_can_loan_messages`
 
 use std::{
     ffi::CString,
@@ -32,8 +32,8 @@ use anyhow::{Context, Result};
 use rclrust_msg::_core::MessageT;
 
 use crate::{
-    error::ToRclRustResult, internal::ffi::*, log::Logger, node::RclNode, qos::QoSProfile,
-    rclrust_error,
+    error::ToRclRustResult, internal::ffi::*, log::Logger, message_info::Gid, node::RclNode,
+    qos::QoSProfile, rclrust_error, serialized_message::RclSerializedMessage,
 };
 
 #[derive(Debug)]
@@ -50,6 +50,15 @@ impl RclPublisher {
     where
         T: MessageT,
     {
+        Self::new_with_type_support(node, T::type_support(), topic_name, qos)
+    }
+
+    pub fn new_with_type_support(
+        node: Arc<Mutex<RclNode>>,
+        type_support: *const c_void,
+        topic_name: &str,
+        qos: &QoSProfile,
+    ) -> Result<Self> {
         let mut publisher = Box::new(unsafe { rcl_sys::rcl_get_zero_initialized_publisher() });
         let topic_c_str = CString::new(topic_name)?;
         let mut options = unsafe { rcl_sys::rcl_publisher_get_default_options() };
@@ -59,12 +68,14 @@ impl RclPublisher {
             rcl_sys::rcl_publisher_init(
                 &mut *publisher,
                 node.lock().unwrap().raw(),
-                T::type_support() as *const _,
+                type_support as *const _,
                 topic_c_str.as_ptr(),
                 &options,
             )
             .to_result()
-            .with_context(|| "rcl_sys::rcl_publisher_init in RclPublisher::new")?;
+            .with_context(|| {
+                "rcl_sys::rcl_publisher_init in RclPublisher::new_with_type_support"
+            })?;
         }
 
         Ok(Self {
@@ -95,6 +106,93 @@ impl RclPublisher {
         Ok(())
     }
 
+    pub fn publish_raw<T>(&self, message: &T::Raw) -> Result<()>
+    where
+        T: MessageT,
+    {
+        unsafe {
+            rcl_sys::rcl_publish(
+                self.raw(),
+                message as *const _ as *const c_void,
+                std::ptr::null_mut(),
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_publish in RclPublisher::publish_raw")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn borrow_loaned_message<T>(&self) -> Result<*mut T::Raw>
+    where
+        T: MessageT,
+    {
+        let mut message = std::ptr::null_mut();
+        unsafe {
+            rcl_sys::rcl_borrow_loaned_message(
+                self.raw(),
+                T::type_support() as *const _,
+                &mut message,
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_borrow_loaned_message in RclPublisher::borrow_loaned_message"
+            })?;
+        }
+
+        Ok(message as *mut T::Raw)
+    }
+
+    pub fn return_loaned_message<T>(&self, message: *mut T::Raw) -> Result<()>
+    where
+        T: MessageT,
+    {
+        unsafe {
+            rcl_sys::rcl_return_loaned_message_from_publisher(self.raw(), message as *mut c_void)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_return_loaned_message_from_publisher in RclPublisher::return_loaned_message"
+                })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn publish_loaned_message<T>(&self, message: *mut T::Raw) -> Result<()>
+    where
+        T: MessageT,
+    {
+        unsafe {
+            rcl_sys::rcl_publish_loaned_message(
+                self.raw(),
+                message as *mut c_void,
+                std::ptr::null_mut(),
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_publish_loaned_message in RclPublisher::publish_loaned_message"
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn publish_serialized(&self, message: &RclSerializedMessage) -> Result<()> {
+        unsafe {
+            rcl_sys::rcl_publish_serialized_message(
+                self.raw(),
+                message.raw(),
+                std::ptr::null_mut(),
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_publish_serialized_message in RclPublisher::publish_serialized"
+            })?;
+        }
+
+        Ok(())
+    }
+
     pub fn topic_name(&self) -> Option<String> {
         unsafe {
             let name = rcl_sys::rcl_publisher_get_topic_name(self.raw());
@@ -125,6 +223,24 @@ impl RclPublisher {
                 .map(|qos| qos.into())
         }
     }
+
+    pub fn can_loan_messages(&self) -> bool {
+        unsafe { rcl_sys::rcl_publisher_can_loan_messages(self.raw()) }
+    }
+
+    pub fn gid(&self) -> Result<Gid> {
+        let mut gid = rcl_sys::rmw_gid_t {
+            implementation_identifier: std::ptr::null(),
+            data: [0; rcl_sys::RMW_GID_STORAGE_SIZE],
+        };
+        unsafe {
+            let rmw_handle = rcl_sys::rcl_publisher_get_rmw_handle(self.raw());
+            rcl_sys::rmw_get_gid_for_publisher(rmw_handle, &mut gid)
+                .to_result()
+                .with_context(|| "rcl_sys::rmw_get_gid_for_publisher in RclPublisher::gid")?;
+        }
+        Ok((&gid).into())
+    }
 }
 
 impl Drop for RclPublisher {
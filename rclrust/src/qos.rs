@@ -1,6 +1,6 @@
 //! QoS (Quality of Service)
 
-use std::time::Duration;
+use std::{fmt, time::Duration};
 
 #[doc(inline)]
 pub use rcl_sys::{
@@ -435,6 +435,400 @@ impl From<&QoSProfile> for rcl_sys::rmw_qos_profile_t {
     }
 }
 
+/// QoS compatibility levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QoSCompatibility {
+    /// QoS policies are compatible
+    Ok,
+    /// QoS policies may not be compatible
+    ///
+    /// Some policies are "system default" or "unknown", so they are resolved only by the middleware.
+    Warning,
+    /// QoS policies are not compatible
+    Error,
+}
+
+/// Result of a QoS compatibility check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QoSCheckCompatibleResult {
+    /// Compatibility result.
+    pub compatibility: QoSCompatibility,
+    /// Reason for a (possible) incompatibility.
+    ///
+    /// Empty if `compatibility` is [`QoSCompatibility::Ok`].
+    pub reason: String,
+}
+
+impl QoSProfile {
+    /// Check if the QoS profiles of a publisher and a subscription are compatible.
+    ///
+    /// The rules are the same as `rmw_qos_profile_check_compatible()`, so this works without a
+    /// middleware. If any error is found, warnings are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::qos::{QoSCompatibility, QoSProfile};
+    /// #
+    /// let publisher = QoSProfile::default().best_effort();
+    /// let subscription = QoSProfile::default().reliable();
+    ///
+    /// let result = QoSProfile::check_compatible(&publisher, &subscription);
+    /// assert_eq!(result.compatibility, QoSCompatibility::Error);
+    /// println!("{}", result.reason);
+    /// ```
+    pub fn check_compatible(publisher: &Self, subscription: &Self) -> QoSCheckCompatibleResult {
+        let errors = qos_compatibility_errors(publisher, subscription);
+        if !errors.is_empty() {
+            return QoSCheckCompatibleResult {
+                compatibility: QoSCompatibility::Error,
+                reason: errors.join(" "),
+            };
+        }
+
+        let warnings = qos_compatibility_warnings(publisher, subscription);
+        if !warnings.is_empty() {
+            return QoSCheckCompatibleResult {
+                compatibility: QoSCompatibility::Warning,
+                reason: warnings.join(" "),
+            };
+        }
+
+        QoSCheckCompatibleResult {
+            compatibility: QoSCompatibility::Ok,
+            reason: String::new(),
+        }
+    }
+}
+
+fn qos_compatibility_errors(publisher: &QoSProfile, subscription: &QoSProfile) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    if publisher.reliability == ReliabilityPolicy::BestEffort
+        && subscription.reliability == ReliabilityPolicy::Reliable
+    {
+        errors.push("ERROR: Best effort publisher and reliable subscription;".into());
+    }
+
+    if publisher.durability == DurabilityPolicy::Volatile
+        && subscription.durability == DurabilityPolicy::TransientLocal
+    {
+        errors.push("ERROR: Volatile publisher and transient local subscription;".into());
+    }
+
+    match (
+        publisher.deadline == Duration::ZERO,
+        subscription.deadline == Duration::ZERO,
+    ) {
+        (true, false) => {
+            errors.push("ERROR: Subscription has a deadline, but publisher does not;".into())
+        }
+        (false, false) if subscription.deadline < publisher.deadline => {
+            errors.push("ERROR: Subscription deadline is less than publisher deadline;".into())
+        }
+        _ => (),
+    }
+
+    if publisher.liveliness == LivelinessPolicy::Automatic
+        && subscription.liveliness == LivelinessPolicy::ManualByTopic
+    {
+        errors.push(
+            "ERROR: Publisher's liveliness is automatic and subscription's is manual by topic;"
+                .into(),
+        );
+    }
+
+    match (
+        publisher.liveliness_lease_duration == Duration::ZERO,
+        subscription.liveliness_lease_duration == Duration::ZERO,
+    ) {
+        (true, false) => errors.push(
+            "ERROR: Subscription has a liveliness lease duration, but publisher does not;".into(),
+        ),
+        (false, false)
+            if subscription.liveliness_lease_duration < publisher.liveliness_lease_duration =>
+        {
+            errors.push(
+                "ERROR: Subscription liveliness lease duration is less than publisher;".into(),
+            )
+        }
+        _ => (),
+    }
+
+    errors
+}
+
+fn qos_compatibility_warnings(publisher: &QoSProfile, subscription: &QoSProfile) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    let pub_reliability_unknown = matches!(
+        publisher.reliability,
+        ReliabilityPolicy::SystemDefault | ReliabilityPolicy::Unknown
+    );
+    let sub_reliability_unknown = matches!(
+        subscription.reliability,
+        ReliabilityPolicy::SystemDefault | ReliabilityPolicy::Unknown
+    );
+    if pub_reliability_unknown && sub_reliability_unknown {
+        warnings.push(format!(
+            "WARNING: Publisher reliability is {} and subscription reliability is {};",
+            reliability_to_str(publisher.reliability),
+            reliability_to_str(subscription.reliability)
+        ));
+    } else if pub_reliability_unknown && subscription.reliability == ReliabilityPolicy::Reliable {
+        warnings.push(format!(
+            "WARNING: Reliable subscription, but publisher is {};",
+            reliability_to_str(publisher.reliability)
+        ));
+    } else if publisher.reliability == ReliabilityPolicy::BestEffort && sub_reliability_unknown {
+        warnings.push(format!(
+            "WARNING: Best effort publisher, but subscription is {};",
+            reliability_to_str(subscription.reliability)
+        ));
+    }
+
+    let pub_durability_unknown = matches!(
+        publisher.durability,
+        DurabilityPolicy::SystemDefault | DurabilityPolicy::Unknown
+    );
+    let sub_durability_unknown = matches!(
+        subscription.durability,
+        DurabilityPolicy::SystemDefault | DurabilityPolicy::Unknown
+    );
+    if pub_durability_unknown && sub_durability_unknown {
+        warnings.push(format!(
+            "WARNING: Publisher durability is {} and subscription durability is {};",
+            durability_to_str(publisher.durability),
+            durability_to_str(subscription.durability)
+        ));
+    } else if pub_durability_unknown && subscription.durability == DurabilityPolicy::TransientLocal
+    {
+        warnings.push(format!(
+            "WARNING: Transient local subscription, but publisher is {};",
+            durability_to_str(publisher.durability)
+        ));
+    } else if publisher.durability == DurabilityPolicy::Volatile && sub_durability_unknown {
+        warnings.push(format!(
+            "WARNING: Volatile publisher, but subscription is {};",
+            durability_to_str(subscription.durability)
+        ));
+    }
+
+    let pub_liveliness_unknown = matches!(
+        publisher.liveliness,
+        LivelinessPolicy::SystemDefault | LivelinessPolicy::Unknown
+    );
+    let sub_liveliness_unknown = matches!(
+        subscription.liveliness,
+        LivelinessPolicy::SystemDefault | LivelinessPolicy::Unknown
+    );
+    if pub_liveliness_unknown && sub_liveliness_unknown {
+        warnings.push(format!(
+            "WARNING: Publisher liveliness is {} and subscription liveliness is {};",
+            liveliness_to_str(publisher.liveliness),
+            liveliness_to_str(subscription.liveliness)
+        ));
+    } else if pub_liveliness_unknown && subscription.liveliness == LivelinessPolicy::ManualByTopic {
+        warnings.push(format!(
+            "WARNING: Subscription's liveliness is manual by topic, but publisher's is {};",
+            liveliness_to_str(publisher.liveliness)
+        ));
+    } else if publisher.liveliness == LivelinessPolicy::Automatic && sub_liveliness_unknown {
+        warnings.push(format!(
+            "WARNING: Publisher's liveliness is automatic, but subscription's is {};",
+            liveliness_to_str(subscription.liveliness)
+        ));
+    }
+
+    warnings
+}
+
+/// QoS policy kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QoSPolicyKind {
+    AvoidRosNamespaceConventions,
+    Deadline,
+    Durability,
+    History,
+    Depth,
+    Lifespan,
+    Liveliness,
+    LivelinessLeaseDuration,
+    Reliability,
+}
+
+impl QoSPolicyKind {
+    /// Get the policy name used in parameter names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::qos::QoSPolicyKind;
+    /// #
+    /// assert_eq!(QoSPolicyKind::Reliability.as_str(), "reliability");
+    /// ```
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AvoidRosNamespaceConventions => "avoid_ros_namespace_conventions",
+            Self::Deadline => "deadline",
+            Self::Durability => "durability",
+            Self::History => "history",
+            Self::Depth => "depth",
+            Self::Lifespan => "lifespan",
+            Self::Liveliness => "liveliness",
+            Self::LivelinessLeaseDuration => "liveliness_lease_duration",
+            Self::Reliability => "reliability",
+        }
+    }
+}
+
+impl fmt::Display for QoSPolicyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Options to override QoS policies of a publisher or a subscription with parameters.
+///
+/// Each policy in `policy_kinds` is declared as a read-only parameter named
+/// `qos_overrides.<topic name>.<publisher|subscription>[_<id>].<policy>`, so that the value can
+/// be given by `--ros-args -p` or `--params-file`. Durations are given in nanoseconds.
+///
+/// No policies can be overridden by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QoSOverridingOptions {
+    /// Policies which can be overridden.
+    pub policy_kinds: Vec<QoSPolicyKind>,
+    /// Id to distinguish publishers or subscriptions on the same topic.
+    pub id: String,
+}
+
+impl QoSOverridingOptions {
+    /// Construct a new `QoSOverridingOptions` with the given policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::qos::{QoSOverridingOptions, QoSPolicyKind};
+    /// #
+    /// let options = QoSOverridingOptions::new(&[QoSPolicyKind::Reliability, QoSPolicyKind::Depth]);
+    /// ```
+    pub fn new(policy_kinds: &[QoSPolicyKind]) -> Self {
+        Self {
+            policy_kinds: policy_kinds.to_vec(),
+            id: String::new(),
+        }
+    }
+
+    /// Construct a new `QoSOverridingOptions` with history, depth and reliability policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::qos::QoSOverridingOptions;
+    /// #
+    /// let options = QoSOverridingOptions::with_default_policies();
+    /// assert_eq!(options.policy_kinds.len(), 3);
+    /// ```
+    pub fn with_default_policies() -> Self {
+        Self::new(&[
+            QoSPolicyKind::History,
+            QoSPolicyKind::Depth,
+            QoSPolicyKind::Reliability,
+        ])
+    }
+
+    /// Set the id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::qos::QoSOverridingOptions;
+    /// #
+    /// let options = QoSOverridingOptions::with_default_policies().id("left");
+    /// ```
+    pub fn id(self, id: &str) -> Self {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+}
+
+pub(crate) const fn history_to_str(policy: HistoryPolicy) -> &'static str {
+    match policy {
+        HistoryPolicy::SystemDefault => "system_default",
+        HistoryPolicy::KeepLast => "keep_last",
+        HistoryPolicy::KeepAll => "keep_all",
+        HistoryPolicy::Unknown => "unknown",
+    }
+}
+
+pub(crate) fn history_from_str(s: &str) -> Option<HistoryPolicy> {
+    match s {
+        "system_default" => Some(HistoryPolicy::SystemDefault),
+        "keep_last" => Some(HistoryPolicy::KeepLast),
+        "keep_all" => Some(HistoryPolicy::KeepAll),
+        _ => None,
+    }
+}
+
+pub(crate) fn reliability_from_str(s: &str) -> Option<ReliabilityPolicy> {
+    match s {
+        "system_default" => Some(ReliabilityPolicy::SystemDefault),
+        "reliable" => Some(ReliabilityPolicy::Reliable),
+        "best_effort" => Some(ReliabilityPolicy::BestEffort),
+        _ => None,
+    }
+}
+
+pub(crate) fn durability_from_str(s: &str) -> Option<DurabilityPolicy> {
+    match s {
+        "system_default" => Some(DurabilityPolicy::SystemDefault),
+        "transient_local" => Some(DurabilityPolicy::TransientLocal),
+        "volatile" => Some(DurabilityPolicy::Volatile),
+        _ => None,
+    }
+}
+
+pub(crate) fn liveliness_from_str(s: &str) -> Option<LivelinessPolicy> {
+    match s {
+        "system_default" => Some(LivelinessPolicy::SystemDefault),
+        "automatic" => Some(LivelinessPolicy::Automatic),
+        "manual_by_topic" => Some(LivelinessPolicy::ManualByTopic),
+        _ => None,
+    }
+}
+
+pub(crate) const fn reliability_to_str(policy: ReliabilityPolicy) -> &'static str {
+    match policy {
+        ReliabilityPolicy::SystemDefault => "system_default",
+        ReliabilityPolicy::Reliable => "reliable",
+        ReliabilityPolicy::BestEffort => "best_effort",
+        ReliabilityPolicy::Unknown => "unknown",
+    }
+}
+
+pub(crate) const fn durability_to_str(policy: DurabilityPolicy) -> &'static str {
+    match policy {
+        DurabilityPolicy::SystemDefault => "system_default",
+        DurabilityPolicy::TransientLocal => "transient_local",
+        DurabilityPolicy::Volatile => "volatile",
+        DurabilityPolicy::Unknown => "unknown",
+    }
+}
+
+#[allow(deprecated)]
+pub(crate) const fn liveliness_to_str(policy: LivelinessPolicy) -> &'static str {
+    match policy {
+        LivelinessPolicy::SystemDefault => "system_default",
+        LivelinessPolicy::Automatic => "automatic",
+        LivelinessPolicy::ManualByNode => "manual_by_node",
+        LivelinessPolicy::ManualByTopic => "manual_by_topic",
+        LivelinessPolicy::Unknown => "unknown",
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -610,4 +1004,94 @@ mod test {
         let qos = qos.avoid_ros_namespace_conventions(flag);
         assert_eq!(qos.avoid_ros_namespace_conventions, flag);
     }
+
+    #[test]
+    fn qos_check_compatible_ok() {
+        let result = QoSProfile::check_compatible(&QoSProfile::default(), &QoSProfile::default());
+        assert_eq!(result.compatibility, QoSCompatibility::Warning);
+        assert!(result.reason.contains("liveliness"));
+
+        let qos = QoSProfile::default().liveliness(LivelinessPolicy::Automatic);
+        let result = QoSProfile::check_compatible(&qos, &qos);
+        assert_eq!(result.compatibility, QoSCompatibility::Ok);
+        assert!(result.reason.is_empty());
+
+        let publisher = qos.clone().reliable().transient_local();
+        let subscription = qos.best_effort().volatile();
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Ok);
+    }
+
+    #[test]
+    fn qos_check_compatible_reliability() {
+        let publisher = QoSProfile::default().best_effort();
+        let subscription = QoSProfile::default().reliable();
+
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result.reason.contains("Best effort publisher"));
+    }
+
+    #[test]
+    fn qos_check_compatible_durability() {
+        let publisher = QoSProfile::default().volatile();
+        let subscription = QoSProfile::default().transient_local();
+
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result.reason.contains("Volatile publisher"));
+    }
+
+    #[test]
+    fn qos_check_compatible_deadline() {
+        let publisher = QoSProfile::default().liveliness(LivelinessPolicy::Automatic);
+        let subscription = publisher.clone().deadline(Duration::from_millis(10));
+
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result.reason.contains("publisher does not"));
+
+        let publisher = publisher.deadline(Duration::from_millis(20));
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result.reason.contains("less than publisher deadline"));
+
+        let result = QoSProfile::check_compatible(&subscription, &publisher);
+        assert_eq!(result.compatibility, QoSCompatibility::Ok);
+    }
+
+    #[test]
+    fn qos_check_compatible_liveliness() {
+        let publisher = QoSProfile::default().liveliness(LivelinessPolicy::Automatic);
+        let subscription = QoSProfile::default().liveliness(LivelinessPolicy::ManualByTopic);
+
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result.reason.contains("manual by topic"));
+
+        let publisher = publisher.liveliness_lease_duration(Duration::from_secs(2));
+        let subscription = publisher
+            .clone()
+            .liveliness_lease_duration(Duration::from_secs(1));
+        let result = QoSProfile::check_compatible(&publisher, &subscription);
+        assert_eq!(result.compatibility, QoSCompatibility::Error);
+        assert!(result
+            .reason
+            .contains("lease duration is less than publisher"));
+    }
+
+    #[test]
+    fn qos_check_compatible_system_default() {
+        let result = QoSProfile::check_compatible(
+            &QoSProfile::system_default(),
+            &QoSProfile::system_default(),
+        );
+        assert_eq!(result.compatibility, QoSCompatibility::Warning);
+        assert!(result
+            .reason
+            .contains("Publisher reliability is system_default"));
+        assert!(result
+            .reason
+            .contains("Publisher durability is system_default"));
+    }
 }
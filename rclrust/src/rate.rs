@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use anyhow::{ensure, Result};
+
+use crate::{clock::Clock, context::Context, duration::Duration, time::Time};
+
+/// Helper to run a loop at a fixed rate of a clock
+///
+/// Unlike `std::thread::sleep`, it follows the time of the clock, including simulated time.
+///
+/// # Examples
+///
+/// ```
+/// let ctx = rclrust::init().unwrap();
+/// let node = ctx.create_node("node1").unwrap();
+/// let mut rate = node.create_rate(100.).unwrap();
+/// for _ in 0..3 {
+///     // do something
+///     rate.sleep(&ctx).unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Rate {
+    clock: Arc<Clock>,
+    period: Duration,
+    last_wakeup: Time,
+}
+
+impl Rate {
+    /// Construct a new `Rate` which wakes up every `period` of `clock`.
+    pub fn new(clock: Arc<Clock>, period: Duration) -> Result<Self> {
+        ensure!(period > Duration::ZERO, "period of a rate must be positive");
+
+        let last_wakeup = clock.now()?;
+        Ok(Self {
+            clock,
+            period,
+            last_wakeup,
+        })
+    }
+
+    /// Construct a new `Rate` which wakes up `frequency` times per second of `clock`.
+    pub fn from_frequency(clock: Arc<Clock>, frequency: f64) -> Result<Self> {
+        ensure!(frequency > 0., "frequency of a rate must be positive");
+        Self::new(clock, Duration::from_secs_f64(1. / frequency))
+    }
+
+    /// Get the period.
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Get the clock which this rate follows.
+    pub const fn clock(&self) -> &Arc<Clock> {
+        &self.clock
+    }
+
+    /// Start the next period from now.
+    pub fn reset(&mut self) -> Result<()> {
+        self.last_wakeup = self.clock.now()?;
+        Ok(())
+    }
+
+    /// Sleep until the end of the current period.
+    ///
+    /// Returns `false` without sleeping if the period has already passed, or early if the
+    /// context is shut down.
+    pub fn sleep(&mut self, context: &Context) -> Result<bool> {
+        match self.next_wakeup()? {
+            Some(next_wakeup) => self.clock.sleep_until(next_wakeup, context),
+            None => Ok(false),
+        }
+    }
+
+    /// Asynchronous version of [`Rate::sleep`]
+    pub async fn sleep_async(&mut self, context: &Context) -> Result<bool> {
+        match self.next_wakeup()? {
+            Some(next_wakeup) => self.clock.sleep_until_async(next_wakeup, context).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Advance to the next period and get when it starts, or `None` if it has already started.
+    fn next_wakeup(&mut self) -> Result<Option<Time>> {
+        let now = self.clock.now()?;
+
+        // Restart from now when the time jumps backwards, e.g. while looping a bag.
+        if now < self.last_wakeup {
+            self.last_wakeup = now;
+        }
+
+        let next_wakeup = self.last_wakeup + self.period;
+        self.last_wakeup = next_wakeup;
+
+        if next_wakeup > now {
+            Ok(Some(next_wakeup))
+        } else {
+            // Give up catching up if more than a whole period is behind.
+            if now > next_wakeup + self.period {
+                self.last_wakeup = now;
+            }
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time;
+
+    use super::*;
+    use crate::clock::ClockType;
+
+    #[test]
+    fn rate_keeps_period() -> Result<()> {
+        let ctx = crate::init()?;
+        let clock = Arc::new(Clock::steady()?);
+        let mut rate = Rate::new(Arc::clone(&clock), Duration::from_nanosecs(10_000_000))?;
+
+        let start = clock.now()?;
+        for _ in 0..5 {
+            assert!(rate.sleep(&ctx)?);
+        }
+        assert!((clock.now()? - start)? >= Duration::from_nanosecs(50_000_000));
+
+        std::thread::sleep(time::Duration::from_millis(30));
+        assert!(!rate.sleep(&ctx)?);
+        Ok(())
+    }
+
+    #[test]
+    fn rate_follows_simulated_time() -> Result<()> {
+        let ctx = crate::init()?;
+        let clock = Arc::new(Clock::ros()?);
+        clock.enable_ros_time_override()?;
+        clock.set_ros_time_override(Time::new(10, 0, ClockType::RosTime))?;
+        let mut rate = Rate::new(Arc::clone(&clock), Duration::new(1, 0))?;
+
+        let handle = {
+            let clock = Arc::clone(&clock);
+            std::thread::spawn(move || {
+                std::thread::sleep(time::Duration::from_millis(50));
+                clock.set_ros_time_override(Time::new(11, 0, ClockType::RosTime))
+            })
+        };
+        assert!(rate.sleep(&ctx)?);
+        assert_eq!(clock.now()?, Time::new(11, 0, ClockType::RosTime));
+        handle.join().unwrap()?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rate_sleep_async() -> Result<()> {
+        let ctx = crate::init()?;
+        let clock = Arc::new(Clock::steady()?);
+        let mut rate = Rate::from_frequency(clock, 100.)?;
+        assert!(rate.sleep_async(&ctx).await?);
+        Ok(())
+    }
+
+    #[test]
+    fn sleep_wakes_up_on_shutdown() -> Result<()> {
+        let ctx = crate::init()?;
+        let clock = Clock::ros()?;
+        clock.enable_ros_time_override()?;
+
+        let until = clock.now()? + Duration::new(1, 0);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(time::Duration::from_millis(50));
+                ctx.shutdown("test").unwrap();
+            });
+            assert!(!clock.sleep_until(until, &ctx).unwrap());
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! Wrapper for rmw/serialized_message.h
+
+use std::os::raw::c_void;
+
+use anyhow::{Context, Result};
+use rclrust_msg::_core::MessageT;
+
+use crate::{error::ToRclRustResult, log::Logger, rclrust_error};
+
+#[derive(Debug)]
+pub struct RclSerializedMessage(rcl_sys::rcl_serialized_message_t);
+
+unsafe impl Send for RclSerializedMessage {}
+
+impl RclSerializedMessage {
+    pub fn new(capacity: usize) -> Result<Self> {
+        let mut message = unsafe { rcl_sys::rcutils_get_zero_initialized_uint8_array() };
+
+        unsafe {
+            rcl_sys::rcutils_uint8_array_init(
+                &mut message,
+                capacity,
+                &rcl_sys::rcutils_get_default_allocator(),
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcutils_uint8_array_init in RclSerializedMessage::new")?;
+        }
+
+        Ok(Self(message))
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut message = Self::new(data.len())?;
+        if !data.is_empty() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(data.as_ptr(), message.0.buffer, data.len());
+            }
+        }
+        message.0.buffer_length = data.len();
+
+        Ok(message)
+    }
+
+    #[inline]
+    pub const fn raw(&self) -> &rcl_sys::rcl_serialized_message_t {
+        &self.0
+    }
+
+    // `&mut` in `const fn` is not available in the MSRV
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn raw_mut(&mut self) -> &mut rcl_sys::rcl_serialized_message_t {
+        &mut self.0
+    }
+
+    // `slice::from_raw_parts` in `const fn` is not available in the MSRV
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.0.buffer.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.0.buffer, self.0.buffer_length) }
+        }
+    }
+
+    /// Deserialize the message as `T`.
+    pub fn deserialize<T>(&self) -> Result<T>
+    where
+        T: MessageT,
+    {
+        let mut message = T::Raw::default();
+        unsafe {
+            rcl_sys::rmw_deserialize(
+                &self.0,
+                T::type_support() as *const rcl_sys::rosidl_message_type_support_t,
+                &mut message as *mut _ as *mut c_void,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rmw_deserialize in RclSerializedMessage::deserialize")?;
+            Ok(T::from_raw(&message))
+        }
+    }
+}
+
+impl Drop for RclSerializedMessage {
+    fn drop(&mut self) {
+        if let Err(e) = unsafe { rcl_sys::rcutils_uint8_array_fini(&mut self.0).to_result() } {
+            rclrust_error!(
+                Logger::new("rclrust"),
+                "Failed to clean up rcl serialized message: {}",
+                e
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serialized_message_from_bytes() -> Result<()> {
+        let data = [0, 1, 0, 0, 42, 0, 0, 0];
+        let message = RclSerializedMessage::from_bytes(&data)?;
+        assert_eq!(message.as_bytes(), &data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialized_message_from_empty_bytes() -> Result<()> {
+        let message = RclSerializedMessage::from_bytes(&[])?;
+        assert!(message.as_bytes().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_message() -> Result<()> {
+        use rclrust_msg::std_msgs::msg::Int32;
+
+        let message = RclSerializedMessage::from_bytes(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+        assert_eq!(message.deserialize::<Int32>()?, Int32 { data: 42 });
+
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 use std::{fmt, sync::Arc};
 
 use anyhow::Result;
-use futures::channel::mpsc;
 use rclrust_msg::_core::ServiceT;
 
 use super::{ChannelMessage, RclService, Service};
-use crate::{error::RclRustError, internal::worker::WorkerMessage, rclrust_debug, Logger};
+use crate::{
+    error::RclRustError,
+    internal::worker::{WorkerSendError, WorkerSender},
+    rclrust_debug, Logger,
+};
 
 pub trait ServiceInvokerBase: fmt::Debug {
     fn handle(&self) -> &RclService;
@@ -17,7 +20,7 @@ where
     Srv: ServiceT,
 {
     handle: Arc<RclService>,
-    tx: Option<mpsc::Sender<WorkerMessage<ChannelMessage<Srv>>>>,
+    tx: Option<WorkerSender<ChannelMessage<Srv>>>,
 }
 
 impl<Srv> ServiceInvoker<Srv>
@@ -72,10 +75,10 @@ where
                 }
             };
 
-            match tx.try_send(WorkerMessage::Message(req)) {
+            match tx.send(req) {
                 Ok(_) => (),
-                Err(e) if e.is_disconnected() => self.stop(),
-                Err(_) => {
+                Err(WorkerSendError::Disconnected) => self.stop(),
+                Err(WorkerSendError::Full) => {
                     return Err(RclRustError::MessageQueueIsFull {
                         type_: "Service",
                         name: self.handle.service_name().expect("service should be valid"),
@@ -3,11 +3,12 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use futures::channel::mpsc;
 use rclrust_msg::_core::{MessageT, ServiceT};
 
 use crate::{
-    internal::worker::{ReceiveWorker, WorkerMessage},
+    callback_queue::CallbackQueueOptions,
+    internal::worker::{ReceiveWorker, WorkerSender},
+    message_info::ServiceInfo,
     node::Node,
     qos::QoSProfile,
 };
@@ -19,10 +20,37 @@ pub mod invoker;
 pub use invoker::{ServiceInvoker, ServiceInvokerBase};
 
 type ChannelMessage<Srv> = (
-    rcl_sys::rmw_request_id_t,
+    rcl_sys::rmw_service_info_t,
     <<Srv as ServiceT>::Request as MessageT>::Raw,
 );
 
+/// Options for creating a service
+#[derive(Debug, Clone, Default)]
+pub struct ServiceOptions {
+    /// Queue between the executor and the callback
+    pub callback_queue: CallbackQueueOptions,
+}
+
+impl ServiceOptions {
+    /// Construct a new `ServiceOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the options of the queue between the executor and the callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{callback_queue::CallbackQueueOptions, ServiceOptions};
+    /// #
+    /// let options = ServiceOptions::new().callback_queue(CallbackQueueOptions::new().depth(100));
+    /// ```
+    pub const fn callback_queue(self, callback_queue: CallbackQueueOptions) -> Self {
+        Self { callback_queue }
+    }
+}
+
 /// Service server
 pub struct Service<Srv>
 where
@@ -41,11 +69,13 @@ where
         service_name: &str,
         callback: F,
         qos: &QoSProfile,
+        queue_options: &CallbackQueueOptions,
     ) -> Result<Self>
     where
         <Srv::Request as MessageT>::Raw: 'static,
-        F: Fn(&<Srv::Request as MessageT>::Raw) -> Srv::Response + Send + 'static,
+        F: Fn(&<Srv::Request as MessageT>::Raw, ServiceInfo) -> Srv::Response + Send + 'static,
     {
+        node.resolve_name(service_name, true, false)?;
         let handle = Arc::new(RclService::new::<Srv>(
             node.clone_handle(),
             service_name,
@@ -55,15 +85,17 @@ where
         let callback = {
             let handle = Arc::clone(&handle);
 
-            move |(mut req_header, req)| {
-                let res = (callback)(&req);
-                handle.send_response::<Srv>(&mut req_header, res).unwrap();
+            move |(mut req_header, req): ChannelMessage<Srv>| {
+                let res = (callback)(&req, (&req_header).into());
+                handle
+                    .send_response::<Srv>(&mut req_header.request_id, res)
+                    .unwrap();
             }
         };
 
         Ok(Self {
             handle,
-            worker: ReceiveWorker::new(callback),
+            worker: ReceiveWorker::new(callback, queue_options),
         })
     }
 
@@ -123,7 +155,7 @@ where
         Arc::clone(&self.handle)
     }
 
-    pub(crate) fn clone_tx(&self) -> mpsc::Sender<WorkerMessage<ChannelMessage<Srv>>> {
+    pub(crate) fn clone_tx(&self) -> WorkerSender<ChannelMessage<Srv>> {
         self.worker.clone_tx()
     }
 }
@@ -6,7 +6,7 @@
 //! - [x] `rcl_service_init`
 //! - [x] `rcl_service_fini`
 //! - [x] `rcl_service_get_default_options`
-//! - [ ] `rcl_take_request_with_info`
+//! - [x] `rcl_take_request_with_info`
 //! - [x] `rcl_take_request`
 //! - [x] `rcl_send_response`
 //! - [x] `rcl_service_get_service_name`
@@ -73,20 +73,20 @@ impl RclService {
         &self.r#impl
     }
 
-    pub fn take_request<Srv>(&self) -> Result<(rcl_sys::rmw_request_id_t, ServiceRequestRaw<Srv>)>
+    pub fn take_request<Srv>(&self) -> Result<(rcl_sys::rmw_service_info_t, ServiceRequestRaw<Srv>)>
     where
         Srv: ServiceT,
     {
         let mut request_header = MaybeUninit::uninit();
         let mut request = Default::default();
         unsafe {
-            rcl_sys::rcl_take_request(
+            rcl_sys::rcl_take_request_with_info(
                 self.raw(),
                 request_header.as_mut_ptr(),
                 &mut request as *mut _ as *mut c_void,
             )
             .to_result()
-            .with_context(|| "rcl_sys::rcl_take_request in RclService::take_request")?;
+            .with_context(|| "rcl_sys::rcl_take_request_with_info in RclService::take_request")?;
         }
 
         Ok((unsafe { request_header.assume_init() }, request))
@@ -1,11 +1,15 @@
 use std::{fmt, sync::Arc};
 
 use anyhow::Result;
-use futures::channel::mpsc;
 use rclrust_msg::_core::MessageT;
 
-use super::{RclSubscription, Subscription};
-use crate::{error::RclRustError, internal::worker::WorkerMessage, rclrust_debug, Logger};
+use super::{RawMessage, RclSubscription, SerializedSubscription, Subscription};
+use crate::{
+    error::RclRustError,
+    internal::worker::{WorkerSendError, WorkerSender},
+    intra_process::IntraProcessManager,
+    rclrust_debug, Logger,
+};
 
 pub trait SubscriptionInvokerBase: fmt::Debug {
     fn handle(&self) -> &RclSubscription;
@@ -17,28 +21,30 @@ where
     T: MessageT,
 {
     handle: Arc<RclSubscription>,
-    tx: Option<mpsc::Sender<WorkerMessage<Arc<T::Raw>>>>,
+    tx: Option<WorkerSender<RawMessage<T>>>,
+    /// Manager whose publishers deliver messages to this subscription directly, with the ID of
+    /// this subscription in it
+    intra_process: Option<(Arc<IntraProcessManager>, u64)>,
+    loan_messages: bool,
 }
 
 impl<T> SubscriptionInvoker<T>
 where
-    T: MessageT,
+    T: MessageT + 'static,
 {
     pub fn new_from_target(target: &Subscription<T>) -> Self {
         Self {
             handle: target.clone_handle(),
             tx: Some(target.clone_tx()),
+            intra_process: target.intra_process_registration(),
+            loan_messages: target.loan_messages(),
         }
     }
-
-    fn stop(&mut self) {
-        self.tx.take();
-    }
 }
 
 impl<T> fmt::Debug for SubscriptionInvoker<T>
 where
-    T: MessageT,
+    T: MessageT + 'static,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "SubscriptionInvoker {{{:?}}}", self.handle)
@@ -47,48 +53,106 @@ where
 
 impl<T> SubscriptionInvokerBase for SubscriptionInvoker<T>
 where
-    T: MessageT,
-    T::Raw: 'static,
+    T: MessageT + 'static,
 {
     fn handle(&self) -> &RclSubscription {
         &self.handle
     }
 
     fn invoke(&mut self) -> Result<()> {
-        if let Some(ref mut tx) = self.tx {
-            let msg = match self.handle.take::<T>() {
-                Ok(v) => v,
-                Err(e) => {
-                    return if let Some(RclRustError::RclSubscriptionTakeFailed(_)) =
-                        e.downcast_ref::<RclRustError>()
-                    {
-                        rclrust_debug!(
-                            Logger::new("rclrust"),
-                            "`rcl_wait()` indicate that message is ready, however which incorrect. I know this happens when I use Cyclone DDS."
-                        );
-                        Ok(())
-                    } else {
-                        Err(e)
-                    };
-                }
-            };
-
-            match tx.try_send(WorkerMessage::Message(Arc::new(msg))) {
-                Ok(_) => (),
-                Err(e) if e.is_disconnected() => self.stop(),
-                Err(_) => {
-                    return Err(RclRustError::MessageQueueIsFull {
-                        type_: "Subscription",
-                        name: self
-                            .handle
-                            .topic_name()
-                            .expect("Subscription should be valid"),
-                    }
-                    .into())
-                }
+        // Messages from intra-process publishers have already been delivered directly
+        let intra_process = &self.intra_process;
+        let loan = self.loan_messages;
+        let take = |handle: &_| RawMessage::<T>::take(handle, loan);
+        take_and_send(&self.handle, &mut self.tx, take, |msg| {
+            intra_process
+                .iter()
+                .any(|(manager, id)| manager.delivers_to(&msg.info().publisher_gid, *id))
+        })
+    }
+}
+
+pub struct SerializedSubscriptionInvoker {
+    handle: Arc<RclSubscription>,
+    tx: Option<WorkerSender<Vec<u8>>>,
+}
+
+impl SerializedSubscriptionInvoker {
+    pub fn new_from_target(target: &SerializedSubscription) -> Self {
+        Self {
+            handle: target.clone_handle(),
+            tx: Some(target.clone_tx()),
+        }
+    }
+}
+
+impl fmt::Debug for SerializedSubscriptionInvoker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SerializedSubscriptionInvoker {{{:?}}}", self.handle)
+    }
+}
+
+impl SubscriptionInvokerBase for SerializedSubscriptionInvoker {
+    fn handle(&self) -> &RclSubscription {
+        &self.handle
+    }
+
+    fn invoke(&mut self) -> Result<()> {
+        take_and_send(
+            &self.handle,
+            &mut self.tx,
+            |handle| handle.take_serialized(),
+            |_| false,
+        )
+    }
+}
+
+/// Take a message from `handle` and send it to the worker unless `skip` returns `true`.
+///
+/// `tx` is cleared if the worker is disconnected.
+fn take_and_send<M, Take, Skip>(
+    handle: &Arc<RclSubscription>,
+    tx: &mut Option<WorkerSender<M>>,
+    take: Take,
+    skip: Skip,
+) -> Result<()>
+where
+    Take: FnOnce(&Arc<RclSubscription>) -> Result<M>,
+    Skip: FnOnce(&M) -> bool,
+{
+    if let Some(ref mut sender) = tx {
+        let msg = match take(handle) {
+            Ok(v) => v,
+            Err(e) => {
+                return if let Some(RclRustError::RclSubscriptionTakeFailed(_)) =
+                    e.downcast_ref::<RclRustError>()
+                {
+                    rclrust_debug!(
+                        Logger::new("rclrust"),
+                        "`rcl_wait()` indicate that message is ready, however which incorrect. I know this happens when I use Cyclone DDS."
+                    );
+                    Ok(())
+                } else {
+                    Err(e)
+                };
             }
+        };
+        if skip(&msg) {
+            return Ok(());
         }
 
-        Ok(())
+        match sender.send(msg) {
+            Ok(_) => (),
+            Err(WorkerSendError::Disconnected) => *tx = None,
+            Err(WorkerSendError::Full) => {
+                return Err(RclRustError::MessageQueueIsFull {
+                    type_: "Subscription",
+                    name: handle.topic_name().expect("Subscription should be valid"),
+                }
+                .into())
+            }
+        }
     }
+
+    Ok(())
 }
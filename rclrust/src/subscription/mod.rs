@@ -1,22 +1,103 @@
 //! Subscribe topics
 
-use std::sync::Arc;
+use std::{os::raw::c_void, sync::Arc};
 
 use anyhow::Result;
-use futures::channel::mpsc;
 use rclrust_msg::_core::MessageT;
 
 use crate::{
-    internal::worker::{ReceiveWorker, WorkerMessage},
+    callback_queue::CallbackQueueOptions,
+    internal::worker::{ReceiveWorker, WorkerSender},
+    intra_process::{
+        IntraProcessManager, IntraProcessMessage, IntraProcessSetting, IntraProcessSubscription,
+    },
     node::Node,
-    qos::QoSProfile,
+    qos::{QoSOverridingOptions, QoSProfile},
 };
 
 pub mod rcl_wrapper;
 pub use rcl_wrapper::RclSubscription;
 
+mod raw_message;
+pub use raw_message::RawMessage;
+
 pub mod invoker;
-pub use invoker::{SubscriptionInvoker, SubscriptionInvokerBase};
+pub use invoker::{SerializedSubscriptionInvoker, SubscriptionInvoker, SubscriptionInvokerBase};
+
+/// Options for creating a subscription
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionOptions {
+    /// QoS policies which can be overridden by parameters.
+    pub qos_overriding_options: QoSOverridingOptions,
+    /// Queue between the executor and the callback
+    pub callback_queue: CallbackQueueOptions,
+    /// Whether to receive messages from publishers in the same context directly
+    pub use_intra_process_comms: IntraProcessSetting,
+}
+
+impl SubscriptionOptions {
+    /// Construct a new `SubscriptionOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the QoS overriding options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{qos::QoSOverridingOptions, SubscriptionOptions};
+    /// #
+    /// let options = SubscriptionOptions::new()
+    ///     .qos_overriding_options(QoSOverridingOptions::with_default_policies());
+    /// ```
+    pub fn qos_overriding_options(self, qos_overriding_options: QoSOverridingOptions) -> Self {
+        Self {
+            qos_overriding_options,
+            ..self
+        }
+    }
+
+    /// Set the options of the queue between the executor and the callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{
+    /// #     callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+    /// #     SubscriptionOptions,
+    /// # };
+    /// #
+    /// let options = SubscriptionOptions::new().callback_queue(
+    ///     CallbackQueueOptions::new().overflow_policy(QueueOverflowPolicy::DropOldest),
+    /// );
+    /// ```
+    pub fn callback_queue(self, callback_queue: CallbackQueueOptions) -> Self {
+        Self {
+            callback_queue,
+            ..self
+        }
+    }
+
+    /// Set whether to use intra-process communication.
+    ///
+    /// Intra-process messages are queued apart from the ones of the middleware, as many as
+    /// the history depth of the QoS.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{IntraProcessSetting, SubscriptionOptions};
+    /// #
+    /// let options = SubscriptionOptions::new().use_intra_process_comms(IntraProcessSetting::Enable);
+    /// ```
+    pub fn use_intra_process_comms(self, use_intra_process_comms: IntraProcessSetting) -> Self {
+        Self {
+            use_intra_process_comms,
+            ..self
+        }
+    }
+}
 
 /// Subscription
 pub struct Subscription<T>
@@ -24,23 +105,29 @@ where
     T: MessageT,
 {
     handle: Arc<RclSubscription>,
-    worker: ReceiveWorker<Arc<T::Raw>>,
+    worker: ReceiveWorker<RawMessage<T>>,
+    intra_process: Option<IntraProcessSubscription<T>>,
+    /// Whether to take messages loaned from the middleware if it can
+    loan_messages: bool,
 }
 
 impl<T> Subscription<T>
 where
-    T: MessageT,
+    T: MessageT + 'static,
 {
     pub(crate) fn new<F>(
         node: &Node,
         topic_name: &str,
         callback: F,
         qos: &QoSProfile,
+        queue_options: &CallbackQueueOptions,
+        loan_messages: bool,
     ) -> Result<Self>
     where
-        T::Raw: 'static,
-        F: Fn(Arc<T::Raw>) + Send + 'static,
+        T: 'static,
+        F: Fn(RawMessage<T>) + Send + 'static,
     {
+        node.resolve_name(topic_name, false, false)?;
         let handle = Arc::new(RclSubscription::new::<T>(
             node.clone_handle(),
             topic_name,
@@ -49,10 +136,38 @@ where
 
         Ok(Self {
             handle,
-            worker: ReceiveWorker::new(callback),
+            worker: ReceiveWorker::new(callback, queue_options),
+            intra_process: None,
+            loan_messages,
         })
     }
 
+    /// Receive messages of intra-process publishers by `callback`, and ignore the same
+    /// messages coming through the middleware.
+    pub(crate) fn enable_intra_process<F>(
+        &mut self,
+        manager: Arc<IntraProcessManager>,
+        qos: &QoSProfile,
+        callback: F,
+    ) -> Result<()>
+    where
+        F: Fn(IntraProcessMessage<T>) + Send + 'static,
+    {
+        let topic_name = self.topic_name().expect("Subscription should be valid");
+        self.intra_process = Some(IntraProcessSubscription::new(
+            manager,
+            &topic_name,
+            qos,
+            callback,
+        )?);
+        Ok(())
+    }
+
+    /// Check if this subscription uses intra-process communication.
+    pub const fn is_intra_process_enabled(&self) -> bool {
+        self.intra_process.is_some()
+    }
+
     /// Get the topic name which this subscritpion subscibes to.
     ///
     /// #  Examples
@@ -157,7 +272,107 @@ where
         Arc::clone(&self.handle)
     }
 
-    pub(crate) fn clone_tx(&self) -> mpsc::Sender<WorkerMessage<Arc<T::Raw>>> {
+    pub(crate) fn clone_tx(&self) -> WorkerSender<RawMessage<T>> {
+        self.worker.clone_tx()
+    }
+
+    pub(crate) const fn loan_messages(&self) -> bool {
+        self.loan_messages
+    }
+
+    pub(crate) fn intra_process_registration(&self) -> Option<(Arc<IntraProcessManager>, u64)> {
+        self.intra_process
+            .as_ref()
+            .map(IntraProcessSubscription::registration)
+    }
+}
+
+/// Subscription which receives messages as serialized CDR bytes
+///
+/// This is created by [`Node::create_serialized_subscription`] or, when the message type is
+/// only known at runtime, by [`Node::create_generic_subscription`].
+pub struct SerializedSubscription {
+    handle: Arc<RclSubscription>,
+    worker: ReceiveWorker<Vec<u8>>,
+}
+
+impl SerializedSubscription {
+    pub(crate) fn new<F>(
+        node: &Node,
+        type_support: *const c_void,
+        topic_name: &str,
+        callback: F,
+        qos: &QoSProfile,
+        queue_options: &CallbackQueueOptions,
+    ) -> Result<Self>
+    where
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        node.resolve_name(topic_name, false, false)?;
+        let handle = Arc::new(RclSubscription::new_with_type_support(
+            node.clone_handle(),
+            type_support,
+            topic_name,
+            qos,
+        )?);
+
+        Ok(Self {
+            handle,
+            worker: ReceiveWorker::new(callback, queue_options),
+        })
+    }
+
+    /// Get the topic name which this subscritpion subscibes to.
+    ///
+    /// #  Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rclrust::qos::QoSProfile;
+    /// # use rclrust_msg::std_msgs::msg::Int32;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let ctx = rclrust::init()?;
+    /// # let mut node = ctx.create_node("node")?;
+    /// # let callback = |_| ();
+    /// let subscription = node.create_serialized_subscription::<Int32, _>(
+    ///     "message",
+    ///     callback,
+    ///     &QoSProfile::default(),
+    /// )?;
+    /// assert_eq!(&subscription.topic_name().unwrap(), "/message");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn topic_name(&self) -> Option<String> {
+        self.handle.topic_name()
+    }
+
+    /// Check if this subscription is valid or not. Normally, a return value should be `true`.
+    pub fn is_valid(&self) -> bool {
+        self.handle.is_valid()
+    }
+
+    /// Get how many publisher are publishing the topic which this subscription subscribes to.
+    pub fn publisher_count(&self) -> Result<usize> {
+        self.handle.publisher_count()
+    }
+
+    /// Get the actual QoS settings, after the defaults have been determined.
+    pub fn actual_qos(&self) -> Option<QoSProfile> {
+        self.handle.actual_qos()
+    }
+
+    pub(crate) fn create_invoker(&self) -> SerializedSubscriptionInvoker {
+        SerializedSubscriptionInvoker::new_from_target(self)
+    }
+
+    pub(crate) fn clone_handle(&self) -> Arc<RclSubscription> {
+        Arc::clone(&self.handle)
+    }
+
+    pub(crate) fn clone_tx(&self) -> WorkerSender<Vec<u8>> {
         self.worker.clone_tx()
     }
 }
@@ -218,6 +433,395 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn pub_sub_with_info() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            sub_node.create_subscription_with_info(
+                &topic_name,
+                move |topic: Arc<Int32>, info| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(topic.data, 42);
+                    assert!(info.source_timestamp.nanosecs > 0);
+                    assert!(info.received_timestamp.nanosecs >= info.source_timestamp.nanosecs);
+                    assert!(!info.from_intra_process);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_publisher::<Int32>(&topic_name, &QoSProfile::default())?;
+        publisher.publish(&Int32 { data: 42 })?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pub_sub_loaned() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            sub_node.create_raw_message_subscription::<Int32, _>(
+                &topic_name,
+                move |msg| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(msg.data, 42);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_publisher::<Int32>(&topic_name, &QoSProfile::default())?;
+        let mut message = publisher.borrow_loaned_message()?;
+        message.data = 42;
+        message.publish()?;
+        {
+            let mut message = publisher.borrow_loaned_message()?;
+            message.data = 42;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pub_sub_serialized() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            sub_node.create_serialized_subscription::<Int32, _>(
+                &topic_name,
+                move |bytes| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(bytes, vec![0, 1, 0, 0, 42, 0, 0, 0]);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_publisher::<Int32>(&topic_name, &QoSProfile::default())?;
+        publisher.publish(&Int32 { data: 42 })?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pub_serialized_sub() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            sub_node.create_subscription(
+                &topic_name,
+                move |topic: Arc<Int32>| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(topic.data, 42);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_publisher::<Int32>(&topic_name, &QoSProfile::default())?;
+        publisher.publish_serialized(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn generic_pub_sub() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            sub_node.create_generic_subscription(
+                &topic_name,
+                "std_msgs/msg/Int32",
+                move |bytes| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(bytes, vec![0, 1, 0, 0, 42, 0, 0, 0]);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_generic_publisher(
+            &topic_name,
+            "std_msgs/msg/Int32",
+            &QoSProfile::default(),
+        )?;
+        publisher.publish(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pub_sub_intra_process() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        use crate::NodeOptions;
+
+        let ctx = crate::init()?;
+        let options = NodeOptions::new().use_intra_process_comms(true);
+        let mut node = ctx.create_node_with_options(&random_name(), &options)?;
+
+        let topic_name = random_name();
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let subscriber = {
+            let counter = Arc::clone(&counter);
+            node.create_subscription_with_info(
+                &topic_name,
+                move |topic: Arc<Int32>, info| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(topic.data, 42);
+                    assert!(info.from_intra_process);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+        assert!(subscriber.is_intra_process_enabled());
+
+        let publisher = node.create_publisher::<Int32>(&topic_name, &QoSProfile::default())?;
+        assert!(publisher.is_intra_process_enabled());
+        publisher.publish(&Int32 { data: 42 })?;
+        publisher.publish_shared(Arc::new(Int32 { data: 42 }))?;
+        {
+            let mut message = publisher.borrow_loaned_message()?;
+            message.data = 42;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pub_sub_intra_process_and_middleware() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        use crate::IntraProcessSetting;
+
+        let ctx = crate::init()?;
+        let pub_node = ctx.create_node(&random_name())?;
+        let mut sub_node = ctx.create_node(&random_name())?;
+
+        let topic_name = random_name();
+
+        let intra_process_counter = Arc::new(AtomicU32::new(0));
+        let _intra_process_subscriber = {
+            let counter = Arc::clone(&intra_process_counter);
+            sub_node.create_subscription_with_info_and_options(
+                &topic_name,
+                move |_: Arc<Int32>, info| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert!(info.from_intra_process);
+                },
+                &QoSProfile::default(),
+                &SubscriptionOptions::new().use_intra_process_comms(IntraProcessSetting::Enable),
+            )?
+        };
+        let middleware_counter = Arc::new(AtomicU32::new(0));
+        let _middleware_subscriber = {
+            let counter = Arc::clone(&middleware_counter);
+            sub_node.create_subscription_with_info(
+                &topic_name,
+                move |_: Arc<Int32>, info| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert!(!info.from_intra_process);
+                },
+                &QoSProfile::default(),
+            )?
+        };
+
+        let publisher = pub_node.create_publisher_with_options::<Int32>(
+            &topic_name,
+            &QoSProfile::default(),
+            &crate::PublisherOptions::new().use_intra_process_comms(IntraProcessSetting::Enable),
+        )?;
+        publisher.publish(&Int32 { data: 42 })?;
+        publisher.publish_serialized(&[0, 1, 0, 0, 42, 0, 0, 0])?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(intra_process_counter.load(Ordering::Relaxed), 2);
+        assert_eq!(middleware_counter.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn intra_process_transient_local() -> Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicU32, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        use crate::NodeOptions;
+
+        let ctx = crate::init()?;
+        let options = NodeOptions::new().use_intra_process_comms(true);
+        let mut node = ctx.create_node_with_options(&random_name(), &options)?;
+
+        let topic_name = random_name();
+        let qos = QoSProfile::default().keep_last(2).transient_local();
+
+        let publisher = node.create_publisher::<Int32>(&topic_name, &qos)?;
+        for data in 0..3 {
+            publisher.publish(&Int32 { data })?;
+        }
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _subscriber = {
+            let counter = Arc::clone(&counter);
+            node.create_subscription_with_info(
+                &topic_name,
+                move |topic: Arc<Int32>, info| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    assert!(topic.data >= 1);
+                    assert!(info.from_intra_process);
+                },
+                &qos,
+            )?
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn intra_process_unsupported() -> Result<()> {
+        use crate::{error::RclRustError, IntraProcessSetting};
+
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+        let options =
+            SubscriptionOptions::new().use_intra_process_comms(IntraProcessSetting::Enable);
+
+        let error = node
+            .create_subscription_with_options::<Int32, _>(
+                "message",
+                |_| (),
+                &QoSProfile::default().keep_all(),
+                &options,
+            )
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error.downcast_ref::<RclRustError>(),
+            Some(RclRustError::IntraProcessUnsupported(_))
+        ));
+        assert!(node
+            .create_raw_subscription_with_options::<Int32, _>(
+                "message",
+                |_| (),
+                &QoSProfile::default(),
+                &options,
+            )
+            .is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn subscription_topic_name() -> Result<()> {
         let ctx = crate::init()?;
@@ -0,0 +1,127 @@
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    ops::Deref,
+    ptr::{self, NonNull},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use rclrust_msg::_core::MessageT;
+
+use super::RclSubscription;
+use crate::{log::Logger, message_info::MessageInfo, rclrust_error};
+
+enum Storage<T>
+where
+    T: MessageT,
+{
+    Loaned {
+        handle: Arc<RclSubscription>,
+        message: NonNull<T::Raw>,
+    },
+    Owned(T::Raw),
+}
+
+/// Raw message received by a subscription
+///
+/// When the middleware can loan messages, the message refers to memory owned by the middleware
+/// and is returned to it when this is dropped. Otherwise the message is copied out.
+pub struct RawMessage<T>
+where
+    T: MessageT,
+{
+    storage: Storage<T>,
+    info: MessageInfo,
+}
+
+unsafe impl<T> Send for RawMessage<T> where T: MessageT {}
+unsafe impl<T> Sync for RawMessage<T> where T: MessageT {}
+
+impl<T> RawMessage<T>
+where
+    T: MessageT,
+{
+    /// Take a message, which is loaned from the middleware if `loan` is `true` and it can.
+    pub(crate) fn take(handle: &Arc<RclSubscription>, loan: bool) -> Result<Self> {
+        let (storage, info) = if loan && handle.can_loan_messages() {
+            let (message, info) = handle.take_loaned_message::<T>()?;
+            let message = NonNull::new(message).expect("Loaned message should not be null");
+            let storage = Storage::Loaned {
+                handle: Arc::clone(handle),
+                message,
+            };
+            (storage, info)
+        } else {
+            let (message, info) = handle.take::<T>()?;
+            (Storage::Owned(message), info)
+        };
+
+        Ok(Self { storage, info })
+    }
+
+    /// Get the information of the message, such as timestamps and the publisher.
+    pub const fn info(&self) -> &MessageInfo {
+        &self.info
+    }
+
+    /// Check if the message is loaned from the middleware.
+    pub const fn is_loaned(&self) -> bool {
+        matches!(self.storage, Storage::Loaned { .. })
+    }
+
+    /// Convert into the owned raw message, or `None` if it is loaned from the middleware.
+    pub fn into_owned(self) -> Option<T::Raw> {
+        if self.is_loaned() {
+            return None;
+        }
+
+        let this = ManuallyDrop::new(self);
+        match &this.storage {
+            // The message is moved out, and the rest of `this` does not need to be dropped.
+            Storage::Owned(message) => Some(unsafe { ptr::read(message) }),
+            Storage::Loaned { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<T> Deref for RawMessage<T>
+where
+    T: MessageT,
+{
+    type Target = T::Raw;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.storage {
+            Storage::Loaned { message, .. } => unsafe { message.as_ref() },
+            Storage::Owned(message) => message,
+        }
+    }
+}
+
+impl<T> fmt::Debug for RawMessage<T>
+where
+    T: MessageT,
+    T::Raw: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Drop for RawMessage<T>
+where
+    T: MessageT,
+{
+    fn drop(&mut self) {
+        if let Storage::Loaned { handle, message } = &self.storage {
+            if let Err(e) = handle.return_loaned_message::<T>(message.as_ptr()) {
+                rclrust_error!(
+                    Logger::new("rclrust"),
+                    "Failed to return a loaned message: {}",
+                    e
+                )
+            }
+        }
+    }
+}
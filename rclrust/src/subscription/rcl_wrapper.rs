@@ -8,16 +8,16 @@
 //! - [x] `rcl_subscription_get_default_options`
 //! - [x] `rcl_take`
 //! - [ ] `rcl_take_sequence`
-//! - [ ] `rcl_take_serialized_message`
-//! - [ ] `rcl_take_loaned_message`
-//! - [ ] `rcl_return_loaned_message_from_subscription`
+//! - [x] `rcl_take_serialized_message`
+//! - [x] `rcl_take_loaned_message`
+//! - [x] `rcl_return_loaned_message_from_subscription`
 //! - [x] `rcl_subscription_get_topic_name`
 //! - [ ] `rcl_subscription_get_options`
 //! - [ ] `rcl_subscription_get_rmw_handle`
 //! - [x] `rcl_subscription_is_valid`
 //! - [x] `rcl_subscription_get_publisher_count`
 //! - [ ] `rcl_subscription_get_actual_qos`
-//! - [ ] `rcl_subscription_can_loan_messages`
+//! - [x] `rcl_subscription_can_loan_messages`
 
 use std::{
     ffi::CString,
@@ -29,8 +29,8 @@ use anyhow::{Context, Result};
 use rclrust_msg::_core::MessageT;
 
 use crate::{
-    error::ToRclRustResult, internal::ffi::*, log::Logger, node::RclNode, qos::QoSProfile,
-    rclrust_error,
+    error::ToRclRustResult, internal::ffi::*, log::Logger, message_info::MessageInfo,
+    node::RclNode, qos::QoSProfile, rclrust_error, serialized_message::RclSerializedMessage,
 };
 
 #[derive(Debug)]
@@ -51,6 +51,15 @@ impl RclSubscription {
     where
         T: MessageT,
     {
+        Self::new_with_type_support(node, T::type_support(), topic_name, qos)
+    }
+
+    pub(crate) fn new_with_type_support(
+        node: Arc<Mutex<RclNode>>,
+        type_support: *const c_void,
+        topic_name: &str,
+        qos: &QoSProfile,
+    ) -> Result<Self> {
         let mut subscription =
             Box::new(unsafe { rcl_sys::rcl_get_zero_initialized_subscription() });
         let topic_c_str = CString::new(topic_name)?;
@@ -61,12 +70,14 @@ impl RclSubscription {
             rcl_sys::rcl_subscription_init(
                 &mut *subscription,
                 node.lock().unwrap().raw(),
-                T::type_support() as *const _,
+                type_support as *const _,
                 topic_c_str.as_ptr(),
                 &options,
             )
             .to_result()
-            .with_context(|| "rcl_sys::rcl_subscription_init in RclSubscription::new")?;
+            .with_context(|| {
+                "rcl_sys::rcl_subscription_init in RclSubscription::new_with_type_support"
+            })?;
         }
 
         Ok(Self {
@@ -80,23 +91,79 @@ impl RclSubscription {
         &self.r#impl
     }
 
-    pub fn take<T>(&self) -> Result<T::Raw>
+    pub fn take<T>(&self) -> Result<(T::Raw, MessageInfo)>
     where
         T: MessageT,
     {
         let mut message = Default::default();
+        let mut message_info = unsafe { rcl_sys::rmw_get_zero_initialized_message_info() };
         unsafe {
             rcl_sys::rcl_take(
                 self.raw(),
                 &mut message as *mut _ as *mut c_void,
-                std::ptr::null_mut(),
+                &mut message_info,
                 std::ptr::null_mut(),
             )
             .to_result()
             .with_context(|| "rcl_sys::rcl_take in RclSubscription::take")?;
         }
 
-        Ok(message)
+        Ok((message, (&message_info).into()))
+    }
+
+    pub fn take_loaned_message<T>(&self) -> Result<(*mut T::Raw, MessageInfo)>
+    where
+        T: MessageT,
+    {
+        let mut message = std::ptr::null_mut();
+        let mut message_info = unsafe { rcl_sys::rmw_get_zero_initialized_message_info() };
+        unsafe {
+            rcl_sys::rcl_take_loaned_message(
+                self.raw(),
+                &mut message,
+                &mut message_info,
+                std::ptr::null_mut(),
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_take_loaned_message in RclSubscription::take_loaned_message"
+            })?;
+        }
+
+        Ok((message as *mut T::Raw, (&message_info).into()))
+    }
+
+    pub fn return_loaned_message<T>(&self, message: *mut T::Raw) -> Result<()>
+    where
+        T: MessageT,
+    {
+        unsafe {
+            rcl_sys::rcl_return_loaned_message_from_subscription(self.raw(), message as *mut c_void)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_return_loaned_message_from_subscription in RclSubscription::return_loaned_message"
+                })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn take_serialized(&self) -> Result<Vec<u8>> {
+        let mut message = RclSerializedMessage::new(0)?;
+        unsafe {
+            rcl_sys::rcl_take_serialized_message(
+                self.raw(),
+                message.raw_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+            .to_result()
+            .with_context(|| {
+                "rcl_sys::rcl_take_serialized_message in RclSubscription::take_serialized"
+            })?;
+        }
+
+        Ok(message.as_bytes().to_vec())
     }
 
     pub fn topic_name(&self) -> Option<String> {
@@ -129,6 +196,10 @@ impl RclSubscription {
                 .map(|qos| qos.into())
         }
     }
+
+    pub fn can_loan_messages(&self) -> bool {
+        unsafe { rcl_sys::rcl_subscription_can_loan_messages(self.raw()) }
+    }
 }
 
 impl Drop for RclSubscription {
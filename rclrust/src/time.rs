@@ -1,11 +1,22 @@
-use std::{convert::TryInto, time::Duration};
+use std::{
+    cmp::Ordering,
+    convert::{TryFrom, TryInto},
+    ops::{Add, AddAssign, Sub, SubAssign},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use anyhow::{Context, Result};
 use rclrust_msg::builtin_interfaces;
 
-use crate::clock::ClockType;
-
-const S_TO_NS: i64 = 1_000_000_000;
+use crate::{
+    clock::ClockType,
+    duration::{self, S_TO_NS},
+    error::ToRclRustResult,
+};
 
+/// Point in time measured by a clock
+///
+/// Times of different clock types cannot be compared or subtracted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Time {
     pub nanosecs: i64,
@@ -72,21 +83,144 @@ impl Time {
     /// assert_eq!(time.to_ros_msg(), time_msg);
     /// ```
     pub fn to_ros_msg(self) -> builtin_interfaces::msg::Time {
-        let sec = self.nanosecs / S_TO_NS;
-        let nanosec = self.nanosecs % S_TO_NS;
+        let (sec, nanosec) = duration::split_nanosecs(self.nanosecs);
+        builtin_interfaces::msg::Time { sec, nanosec }
+    }
 
-        let (sec, nanosec) = if self.nanosecs >= 0 {
-            (sec, nanosec)
-        } else {
-            (sec - 1, nanosec + S_TO_NS)
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{ClockType, Time};
+    ///
+    /// let time = Time::from_nanosecs(1_500_000_000, ClockType::RosTime);
+    /// assert_eq!(time.to_secs_f64(), 1.5);
+    /// ```
+    pub fn to_secs_f64(self) -> f64 {
+        self.nanosecs as f64 / S_TO_NS as f64
+    }
+
+    /// Get `self - start`, which fails if the clock types are different.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rclrust::{ClockType, Duration, Time};
+    ///
+    /// let start = Time::from_nanosecs(300, ClockType::RosTime);
+    /// let finish = Time::from_nanosecs(100, ClockType::RosTime);
+    /// assert_eq!(
+    ///     finish.difference(start).unwrap(),
+    ///     Duration::from_nanosecs(-200)
+    /// );
+    ///
+    /// let other = Time::from_nanosecs(100, ClockType::SystemTime);
+    /// assert!(other.difference(start).is_err());
+    /// ```
+    pub fn difference(self, start: Self) -> Result<duration::Duration> {
+        let mut start = rcl_sys::rcl_time_point_t::from(start);
+        let mut finish = rcl_sys::rcl_time_point_t::from(self);
+        let mut delta = rcl_sys::rcl_duration_t { nanoseconds: 0 };
+        unsafe {
+            rcl_sys::rcl_difference_times(&mut start, &mut finish, &mut delta)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_difference_times in Time::difference")?;
+        }
+        Ok((&delta).into())
+    }
+}
+
+impl From<Time> for rcl_sys::rcl_time_point_t {
+    fn from(time: Time) -> Self {
+        Self {
+            nanoseconds: time.nanosecs,
+            clock_type: time.clock_type,
+        }
+    }
+}
+
+impl From<SystemTime> for Time {
+    /// Times out of the range of `i64` nanoseconds are saturated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// use rclrust::{ClockType, Time};
+    ///
+    /// let time = Time::from(UNIX_EPOCH + Duration::from_secs(1));
+    /// assert_eq!(time, Time::new(1, 0, ClockType::SystemTime));
+    /// ```
+    fn from(time: SystemTime) -> Self {
+        let nanosecs = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_nanos().try_into().unwrap_or(i64::MAX),
+            Err(e) => -i64::try_from(e.duration().as_nanos()).unwrap_or(i64::MAX),
         };
-        builtin_interfaces::msg::Time {
-            sec: sec.try_into().unwrap(),
-            nanosec: nanosec.try_into().unwrap(),
+        Self::from_nanosecs(nanosecs, ClockType::SystemTime)
+    }
+}
+
+impl PartialOrd for Time {
+    /// Times of different clock types are not comparable.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.clock_type == other.clock_type {
+            Some(self.nanosecs.cmp(&other.nanosecs))
+        } else {
+            None
+        }
+    }
+}
+
+impl Add<duration::Duration> for Time {
+    type Output = Self;
+
+    fn add(self, rhs: duration::Duration) -> Self::Output {
+        Self {
+            nanosecs: self
+                .nanosecs
+                .checked_add(rhs.nanosecs)
+                .expect("overflow when adding duration to time"),
+            ..self
+        }
+    }
+}
+
+impl AddAssign<duration::Duration> for Time {
+    fn add_assign(&mut self, rhs: duration::Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<duration::Duration> for Time {
+    type Output = Self;
+
+    fn sub(self, rhs: duration::Duration) -> Self::Output {
+        Self {
+            nanosecs: self
+                .nanosecs
+                .checked_sub(rhs.nanosecs)
+                .expect("overflow when subtracting duration from time"),
+            ..self
         }
     }
 }
 
+impl SubAssign<duration::Duration> for Time {
+    fn sub_assign(&mut self, rhs: duration::Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Time {
+    type Output = Result<duration::Duration>;
+
+    /// Same as [`Time::difference`], which fails if the clock types are different like
+    /// [`PartialOrd`] does not compare them.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
 pub(crate) trait RclDurationT {
     fn to_rmw_time_t(&self) -> rcl_sys::rmw_time_t;
 
@@ -105,3 +239,37 @@ impl RclDurationT for Duration {
         Self::new(duration.sec, duration.nsec.try_into().unwrap())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn time_arithmetic() -> Result<()> {
+        let time = Time::new(1, 0, ClockType::RosTime);
+        let later = time + duration::Duration::from_nanosecs(500);
+        assert_eq!(later.nanosecs, S_TO_NS + 500);
+        assert_eq!((later - time)?, duration::Duration::from_nanosecs(500));
+        assert_eq!((time - later)?, duration::Duration::from_nanosecs(-500));
+        assert_eq!(later - duration::Duration::from_nanosecs(500), time);
+        assert!(time < later);
+        Ok(())
+    }
+
+    #[test]
+    fn compare_different_clock_types() {
+        let ros_time = Time::from_nanosecs(0, ClockType::RosTime);
+        let system_time = Time::from_nanosecs(1, ClockType::SystemTime);
+        assert_eq!(ros_time.partial_cmp(&system_time), None);
+        assert!(system_time.difference(ros_time).is_err());
+        assert!((system_time - ros_time).is_err());
+    }
+
+    #[test]
+    fn negative_time_to_ros_msg() {
+        let time = Time::from_nanosecs(-1, ClockType::RosTime);
+        let time_msg = time.to_ros_msg();
+        assert_eq!((time_msg.sec, time_msg.nanosec), (-1, 999_999_999));
+        assert_eq!(Time::from_ros_msg(&time_msg, ClockType::RosTime), time);
+    }
+}
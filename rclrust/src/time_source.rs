@@ -0,0 +1,250 @@
+//! Source of ROS time, which follows the `/clock` topic while `use_sim_time` is enabled
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, Weak},
+};
+
+use anyhow::{ensure, Result};
+use rclrust_msg::{_core::MessageT, rosgraph_msgs::msg::Clock as ClockMsg};
+
+use crate::{
+    callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+    clock::{Clock, ClockType},
+    executor::ExecutorMessage,
+    log::Logger,
+    node::Node,
+    qos::QoSProfile,
+    rclrust_error,
+    subscription::Subscription,
+    time::Time,
+};
+
+/// Name of the parameter which enables simulated time
+pub const USE_SIM_TIME_PARAMETER: &str = "use_sim_time";
+
+/// Topic which simulated time is published to
+pub const CLOCK_TOPIC: &str = "/clock";
+
+#[derive(Debug, Default)]
+struct State {
+    clocks: Vec<Weak<Clock>>,
+    ros_time_active: bool,
+    last_time: Option<Time>,
+}
+
+impl State {
+    fn attached_clocks(&mut self) -> Vec<Arc<Clock>> {
+        self.clocks.retain(|clock| clock.strong_count() > 0);
+        self.clocks.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    fn apply(&self, clock: &Clock) -> Result<()> {
+        if self.ros_time_active {
+            clock.enable_ros_time_override()?;
+            if let Some(time) = self.last_time {
+                clock.set_ros_time_override(time)?;
+            }
+        } else {
+            clock.disable_ros_time_override()?;
+        }
+        Ok(())
+    }
+
+    fn set_ros_time_active(&mut self, active: bool) -> Result<()> {
+        self.ros_time_active = active;
+        self.attached_clocks()
+            .iter()
+            .try_for_each(|clock| self.apply(clock))
+    }
+
+    fn set_time(&mut self, time: Time) -> Result<()> {
+        self.last_time = Some(time);
+        if !self.ros_time_active {
+            return Ok(());
+        }
+        self.attached_clocks()
+            .iter()
+            .try_for_each(|clock| clock.set_ros_time_override(time))
+    }
+}
+
+/// Drives the ROS clocks attached to a node.
+///
+/// While the `use_sim_time` parameter of the node is `true`, the ROS time override of the
+/// attached clocks is enabled and they follow the time published on `/clock`. Otherwise they
+/// follow the system time.
+#[derive(Default)]
+pub struct TimeSource {
+    state: Arc<Mutex<State>>,
+    clock_subscription: Mutex<Option<Subscription<ClockMsg>>>,
+}
+
+impl fmt::Debug for TimeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TimeSource {{{:?}}}", self.state)
+    }
+}
+
+impl TimeSource {
+    pub(crate) fn new() -> Self {
+        Default::default()
+    }
+
+    /// Attach a ROS clock, which then follows simulated time while it is enabled.
+    ///
+    /// The clock is detached automatically when it is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use rclrust::Clock;
+    ///
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node("node1").unwrap();
+    /// let clock = Arc::new(Clock::ros().unwrap());
+    /// node.time_source().attach_clock(&clock).unwrap();
+    /// ```
+    pub fn attach_clock(&self, clock: &Arc<Clock>) -> Result<()> {
+        ensure!(
+            clock.clock_type() == ClockType::RosTime,
+            "only `ClockType::RosTime` clocks can be attached to a time source"
+        );
+
+        let mut state = self.state.lock().unwrap();
+        state.apply(clock)?;
+        state.clocks.push(Arc::downgrade(clock));
+        drop(state);
+        Ok(())
+    }
+
+    /// Check if the attached clocks follow simulated time or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ctx = rclrust::init().unwrap();
+    /// let node = ctx.create_node("node1").unwrap();
+    /// assert!(!node.time_source().is_ros_time_active());
+    /// ```
+    pub fn is_ros_time_active(&self) -> bool {
+        self.state.lock().unwrap().ros_time_active
+    }
+
+    /// Switch between simulated time and the system time, and subscribe to `/clock` only
+    /// while simulated time is used.
+    pub(crate) fn set_use_sim_time(&self, node: &Node, use_sim_time: bool) -> Result<()> {
+        let mut clock_subscription = self.clock_subscription.lock().unwrap();
+        if use_sim_time && clock_subscription.is_none() {
+            let state = Arc::clone(&self.state);
+            let subscription = Subscription::<ClockMsg>::new(
+                node,
+                CLOCK_TOPIC,
+                move |msg| {
+                    let msg = unsafe { ClockMsg::from_raw(&msg) };
+                    let time = Time::from_ros_msg(&msg.clock, ClockType::RosTime);
+                    let result = state.lock().unwrap().set_time(time);
+                    if let Err(e) = result {
+                        rclrust_error!(Logger::new("rclrust"), "Failed to update ROS time: {}", e)
+                    }
+                },
+                // Same as rclcpp, which can receive from both reliable and best-effort publishers
+                &QoSProfile::default().keep_last(1).best_effort(),
+                &CallbackQueueOptions::new()
+                    .depth(1)
+                    .overflow_policy(QueueOverflowPolicy::DropOldest),
+                true,
+            )?;
+            node.add_to_executor(ExecutorMessage::Subscription(Box::new(
+                subscription.create_invoker(),
+            )))?;
+            *clock_subscription = Some(subscription);
+        } else if !use_sim_time {
+            clock_subscription.take();
+        }
+        drop(clock_subscription);
+
+        self.state.lock().unwrap().set_ros_time_active(use_sim_time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Parameter;
+
+    #[test]
+    fn attach_clock() -> Result<()> {
+        let time_source = TimeSource::new();
+        let clock = Arc::new(Clock::ros()?);
+        time_source.attach_clock(&clock)?;
+
+        let mut state = time_source.state.lock().unwrap();
+        state.set_ros_time_active(true)?;
+        state.set_time(Time::from_nanosecs(100, ClockType::RosTime))?;
+        assert_eq!(clock.now()?.nanosecs, 100);
+
+        state.set_ros_time_active(false)?;
+        assert!(!clock.is_ros_time_override_enabled()?);
+
+        drop(clock);
+        assert!(state.attached_clocks().is_empty());
+        drop(state);
+        Ok(())
+    }
+
+    #[test]
+    fn attach_non_ros_clock() -> Result<()> {
+        let time_source = TimeSource::new();
+        let clock = Arc::new(Clock::steady()?);
+        assert!(time_source.attach_clock(&clock).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn follow_clock_topic() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("time_source_node")?;
+        node.set_parameter(Parameter::bool(USE_SIM_TIME_PARAMETER, true))?;
+        assert!(node.time_source().is_ros_time_active());
+
+        let clock = Arc::new(Clock::ros()?);
+        node.time_source().attach_clock(&clock)?;
+
+        let publisher = node.create_publisher::<ClockMsg>(CLOCK_TOPIC, &QoSProfile::default())?;
+        let msg = ClockMsg {
+            clock: Time::new(1234, 0, ClockType::RosTime).to_ros_msg(),
+        };
+        for _ in 0..10 {
+            publisher.publish(&msg)?;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(clock.now()?, Time::new(1234, 0, ClockType::RosTime));
+
+        node.set_parameter(Parameter::bool(USE_SIM_TIME_PARAMETER, false))?;
+        assert!(!clock.is_ros_time_override_enabled()?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keep_use_sim_time_on_failure() -> Result<()> {
+        let ctx = crate::init()?;
+        let node = ctx.create_node("time_source_node")?;
+        ctx.shutdown("test")?;
+
+        assert!(node
+            .set_parameter(Parameter::bool(USE_SIM_TIME_PARAMETER, true))
+            .is_err());
+        assert_eq!(
+            node.get_parameter(USE_SIM_TIME_PARAMETER).unwrap().value,
+            crate::ParameterValue::bool(false)
+        );
+        assert!(!node.time_source().is_ros_time_active());
+
+        Ok(())
+    }
+}
@@ -6,46 +6,54 @@ use std::{
 };
 
 use anyhow::{Context, Result};
-use futures::channel::mpsc;
 
 use crate::{
+    callback_queue::CallbackQueueOptions,
     clock::{Clock, ClockType},
     context::RclContext,
     error::{RclRustError, ToRclRustResult},
-    internal::worker::{ReceiveWorker, WorkerMessage},
+    internal::worker::{ReceiveWorker, WorkerSendError, WorkerSender},
     log::Logger,
     node::Node,
     rclrust_error,
 };
 
 #[derive(Debug)]
-pub struct RclTimer(Box<rcl_sys::rcl_timer_t>);
+pub struct RclTimer {
+    r#impl: Box<rcl_sys::rcl_timer_t>,
+    /// The rcl timer refers to the clock, so it has to outlive the timer.
+    clock: Arc<Clock>,
+}
 
 unsafe impl Send for RclTimer {}
 
 impl RclTimer {
-    fn new(clock: &mut Clock, context: &mut RclContext, period: Duration) -> Result<Self> {
+    fn new(clock: Arc<Clock>, context: &mut RclContext, period: Duration) -> Result<Self> {
         let mut timer = Box::new(unsafe { rcl_sys::rcl_get_zero_initialized_timer() });
 
-        unsafe {
+        // `rcl_timer_init()` registers a jump callback on a ROS clock.
+        clock.with_raw_locked(|raw_clock| unsafe {
             rcl_sys::rcl_timer_init(
                 &mut *timer,
-                clock.raw_mut(),
+                raw_clock,
                 context.raw_mut(),
                 period.as_nanos().try_into().unwrap(),
                 None,
                 rcl_sys::rcutils_get_default_allocator(),
             )
             .to_result()
-            .with_context(|| "rcl_sys::rcl_timer_init in RclTimer::new")?;
-        }
+            .with_context(|| "rcl_sys::rcl_timer_init in RclTimer::new")
+        })?;
 
-        Ok(Self(timer))
+        Ok(Self {
+            r#impl: timer,
+            clock,
+        })
     }
 
     #[inline]
     pub const fn raw(&self) -> &rcl_sys::rcl_timer_t {
-        &self.0
+        &self.r#impl
     }
 
     #[allow(dead_code)]
@@ -61,16 +69,100 @@ impl RclTimer {
 
     fn call(&mut self) -> Result<()> {
         unsafe {
-            rcl_sys::rcl_timer_call(&mut *self.0)
+            rcl_sys::rcl_timer_call(&mut *self.r#impl)
                 .to_result()
                 .with_context(|| "rcl_sys::rcl_timer_call in RclTimer::call")
         }
     }
+
+    fn cancel(&mut self) -> Result<()> {
+        unsafe {
+            rcl_sys::rcl_timer_cancel(&mut *self.r#impl)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_timer_cancel in RclTimer::cancel")
+        }
+    }
+
+    fn is_canceled(&self) -> Result<bool> {
+        let mut canceled = false;
+        unsafe {
+            rcl_sys::rcl_timer_is_canceled(self.raw(), &mut canceled)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_timer_is_canceled in RclTimer::is_canceled")?;
+        }
+        Ok(canceled)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        unsafe {
+            rcl_sys::rcl_timer_reset(&mut *self.r#impl)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_timer_reset in RclTimer::reset")
+        }
+    }
+
+    fn period(&self) -> Result<Duration> {
+        let mut period = 0;
+        unsafe {
+            rcl_sys::rcl_timer_get_period(self.raw(), &mut period)
+                .to_result()
+                .with_context(|| "rcl_sys::rcl_timer_get_period in RclTimer::period")?;
+        }
+        Ok(nanosecs_to_duration(period))
+    }
+
+    fn exchange_period(&self, period: Duration) -> Result<Duration> {
+        let mut old_period = 0;
+        unsafe {
+            rcl_sys::rcl_timer_exchange_period(
+                self.raw(),
+                period.as_nanos().try_into()?,
+                &mut old_period,
+            )
+            .to_result()
+            .with_context(|| "rcl_sys::rcl_timer_exchange_period in RclTimer::exchange_period")?;
+        }
+        Ok(nanosecs_to_duration(old_period))
+    }
+
+    fn time_until_next_call(&self) -> Result<Duration> {
+        let mut time = 0;
+        unsafe {
+            rcl_sys::rcl_timer_get_time_until_next_call(self.raw(), &mut time)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_timer_get_time_until_next_call in RclTimer::time_until_next_call"
+                })?;
+        }
+        Ok(nanosecs_to_duration(time))
+    }
+
+    fn time_since_last_call(&self) -> Result<Duration> {
+        let mut time = 0;
+        unsafe {
+            rcl_sys::rcl_timer_get_time_since_last_call(self.raw(), &mut time)
+                .to_result()
+                .with_context(|| {
+                    "rcl_sys::rcl_timer_get_time_since_last_call in RclTimer::time_since_last_call"
+                })?;
+        }
+        Ok(nanosecs_to_duration(time))
+    }
+}
+
+/// Negative values, e.g. the time until an overdue call, are clamped to zero.
+fn nanosecs_to_duration(nanosecs: i64) -> Duration {
+    Duration::from_nanos(nanosecs.try_into().unwrap_or(0))
 }
 
 impl Drop for RclTimer {
     fn drop(&mut self) {
-        if let Err(e) = unsafe { rcl_sys::rcl_timer_fini(&mut *self.0).to_result() } {
+        // `rcl_timer_fini()` removes the jump callback registered on a ROS clock.
+        let timer = &mut *self.r#impl;
+        let result = self
+            .clock
+            .with_raw_locked(|_| unsafe { rcl_sys::rcl_timer_fini(timer).to_result() });
+        if let Err(e) = result {
             rclrust_error!(
                 Logger::new("rclrust"),
                 "Failed to clean up rcl timer handle: {}",
@@ -80,10 +172,61 @@ impl Drop for RclTimer {
     }
 }
 
+/// Options for creating a timer
+#[derive(Debug, Clone, Default)]
+pub struct TimerOptions {
+    /// Queue between the executor and the callback
+    pub callback_queue: CallbackQueueOptions,
+    /// If `true`, the timer is canceled after the first call. It can be re-armed by
+    /// [`Timer::reset`].
+    pub one_shot: bool,
+}
+
+impl TimerOptions {
+    /// Construct a new `TimerOptions` with default values.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the options of the queue between the executor and the callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::{
+    /// #     callback_queue::{CallbackQueueOptions, QueueOverflowPolicy},
+    /// #     timer::TimerOptions,
+    /// # };
+    /// #
+    /// let options = TimerOptions::new().callback_queue(
+    ///     CallbackQueueOptions::new().overflow_policy(QueueOverflowPolicy::DropNewest),
+    /// );
+    /// ```
+    pub const fn callback_queue(self, callback_queue: CallbackQueueOptions) -> Self {
+        Self {
+            callback_queue,
+            ..self
+        }
+    }
+
+    /// Make the timer fire only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rclrust::timer::TimerOptions;
+    /// #
+    /// let options = TimerOptions::new().one_shot(true);
+    /// ```
+    pub const fn one_shot(self, one_shot: bool) -> Self {
+        Self { one_shot, ..self }
+    }
+}
+
 pub struct Timer {
     handle: Arc<Mutex<RclTimer>>,
-    _clock: Box<Clock>,
     worker: ReceiveWorker<()>,
+    one_shot: bool,
 }
 
 impl Timer {
@@ -92,27 +235,72 @@ impl Timer {
         period: Duration,
         clock_type: ClockType,
         callback: F,
+        options: &TimerOptions,
     ) -> Result<Arc<Self>>
     where
         F: Fn() + Send + 'static,
     {
-        let mut clock = Box::new(Clock::new(clock_type)?);
+        let clock = match clock_type {
+            ClockType::RosTime => node.get_clock(),
+            _ => Arc::new(Clock::new(clock_type)?),
+        };
+
         let handle = Arc::new(Mutex::new(RclTimer::new(
-            &mut clock,
+            clock,
             &mut node.context.lock().unwrap(),
             period,
         )?));
         Ok(Arc::new(Self {
             handle,
-            _clock: clock,
-            worker: ReceiveWorker::new(move |_| callback()),
+            worker: ReceiveWorker::new(move |_| callback(), &options.callback_queue),
+            one_shot: options.one_shot,
         }))
     }
 
+    /// Cancel the timer. The callback is not called until the timer is reset.
+    pub fn cancel(&self) -> Result<()> {
+        self.handle.lock().unwrap().cancel()
+    }
+
+    /// Check if the timer is canceled or not.
+    pub fn is_canceled(&self) -> Result<bool> {
+        self.handle.lock().unwrap().is_canceled()
+    }
+
+    /// Reset the timer, which also re-arms a canceled timer.
+    ///
+    /// The next call is scheduled after a period from now.
+    pub fn reset(&self) -> Result<()> {
+        self.handle.lock().unwrap().reset()
+    }
+
+    /// Get the period of the timer.
+    pub fn period(&self) -> Result<Duration> {
+        self.handle.lock().unwrap().period()
+    }
+
+    /// Change the period of the timer and return the previous one.
+    ///
+    /// The new period takes effect after the next call.
+    pub fn change_period(&self, period: Duration) -> Result<Duration> {
+        self.handle.lock().unwrap().exchange_period(period)
+    }
+
+    /// Get the time until the next call. It is zero when the call is overdue.
+    pub fn time_until_next_call(&self) -> Result<Duration> {
+        self.handle.lock().unwrap().time_until_next_call()
+    }
+
+    /// Get the time since the previous call, or since the timer was created or reset.
+    pub fn time_since_last_call(&self) -> Result<Duration> {
+        self.handle.lock().unwrap().time_since_last_call()
+    }
+
     pub(crate) fn create_invoker(&self) -> TimerInvoker {
         TimerInvoker {
             handle: self.clone_handle(),
             tx: Some(self.clone_tx()),
+            one_shot: self.one_shot,
         }
     }
 
@@ -120,14 +308,15 @@ impl Timer {
         Arc::clone(&self.handle)
     }
 
-    pub(crate) fn clone_tx(&self) -> mpsc::Sender<WorkerMessage<()>> {
+    pub(crate) fn clone_tx(&self) -> WorkerSender<()> {
         self.worker.clone_tx()
     }
 }
 
 pub(crate) struct TimerInvoker {
     pub handle: Arc<Mutex<RclTimer>>,
-    tx: Option<mpsc::Sender<WorkerMessage<()>>>,
+    tx: Option<WorkerSender<()>>,
+    one_shot: bool,
 }
 
 impl fmt::Debug for TimerInvoker {
@@ -142,12 +331,24 @@ impl TimerInvoker {
     }
 
     pub fn invoke(&mut self) -> Result<()> {
-        self.handle.lock().unwrap().call()?;
+        {
+            let mut handle = self.handle.lock().unwrap();
+            if let Err(e) = handle.call() {
+                // The timer may be canceled by a user after `rcl_wait()`.
+                return match e.downcast_ref::<RclRustError>() {
+                    Some(RclRustError::RclTimerCanceled(_)) => Ok(()),
+                    _ => Err(e),
+                };
+            }
+            if self.one_shot {
+                handle.cancel()?;
+            }
+        }
         if let Some(ref mut tx) = self.tx {
-            match tx.try_send(WorkerMessage::Message(())) {
+            match tx.send(()) {
                 Ok(_) => (),
-                Err(e) if e.is_disconnected() => self.stop(),
-                Err(_) => {
+                Err(WorkerSendError::Disconnected) => self.stop(),
+                Err(WorkerSendError::Full) => {
                     return Err(RclRustError::MessageQueueIsFull {
                         type_: "Timer",
                         name: "<none>".into(),
@@ -160,3 +361,141 @@ impl TimerInvoker {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::time::Time;
+
+    fn random_name() -> String {
+        use rand::{distributions::Alphanumeric, thread_rng, Rng};
+
+        thread_rng()
+            .sample_iter(&Alphanumeric)
+            .map(char::from)
+            .filter(|c| c.is_alphabetic())
+            .take(20)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn timer_cancel_and_reset() -> Result<()> {
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+        let timer = node.create_wall_timer(Duration::from_millis(10), || ())?;
+
+        assert!(!timer.is_canceled()?);
+        timer.cancel()?;
+        assert!(timer.is_canceled()?);
+        timer.reset()?;
+        assert!(!timer.is_canceled()?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_timers_in_callback() -> Result<()> {
+        use std::sync::Mutex;
+
+        use tokio::sync::mpsc;
+
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+        // More entities than the executor handles at once, created on the executor thread.
+        let sub_node = Arc::new(Mutex::new(Some(node.create_sub_node("sub")?)));
+        let (tx, mut rx) = mpsc::channel(1);
+        let _timer = node.create_wall_timer(Duration::from_millis(10), {
+            let sub_node = Arc::clone(&sub_node);
+            move || {
+                if let Some(sub_node) = sub_node.lock().unwrap().as_mut() {
+                    let timers = (0..32)
+                        .map(|_| sub_node.create_wall_timer(Duration::from_secs(1), || ()))
+                        .collect::<Result<Vec<_>>>();
+                    let _ = tx.try_send(timers.map(|timers| timers.len()));
+                }
+            }
+        })?;
+
+        let timers = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await?;
+        assert_eq!(timers.unwrap()?, 32);
+        // The sub-node in the callback would keep the executor alive.
+        sub_node.lock().unwrap().take();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timer_change_period() -> Result<()> {
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+        let timer = node.create_wall_timer(Duration::from_millis(10), || ())?;
+
+        assert_eq!(timer.period()?, Duration::from_millis(10));
+        assert_eq!(
+            timer.change_period(Duration::from_millis(20))?,
+            Duration::from_millis(10)
+        );
+        assert_eq!(timer.period()?, Duration::from_millis(20));
+        assert!(timer.time_until_next_call()? <= Duration::from_millis(10));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timer_one_shot() -> Result<()> {
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let timer = {
+            let counter = Arc::clone(&counter);
+            node.create_timer_with_options(
+                Duration::from_millis(10),
+                ClockType::SteadyTime,
+                move || {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                },
+                &TimerOptions::new().one_shot(true),
+            )?
+        };
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+        assert!(timer.is_canceled()?);
+
+        timer.reset()?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ros_timer_follows_node_clock() -> Result<()> {
+        let ctx = crate::init()?;
+        let mut node = ctx.create_node(&random_name())?;
+        let clock = node.get_clock();
+        clock.enable_ros_time_override()?;
+        clock.set_ros_time_override(Time::from_nanosecs(0, ClockType::RosTime))?;
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let _timer = {
+            let counter = Arc::clone(&counter);
+            node.create_timer(Duration::from_secs(1), ClockType::RosTime, move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            })?
+        };
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+        clock.set_ros_time_override(Time::new(1, 0, ClockType::RosTime))?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+        assert_eq!(node.now()?, Time::new(1, 0, ClockType::RosTime));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Load message type supports at runtime from `<package>__rosidl_typesupport_c` libraries
+
+use std::{env, os::raw::c_void, path::PathBuf};
+
+use anyhow::{Context, Result};
+use libloading::{Library, Symbol};
+
+use crate::{error::RclRustError, internal::globals};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TypeName<'a> {
+    package: &'a str,
+    namespace: &'a str,
+    name: &'a str,
+}
+
+impl<'a> TypeName<'a> {
+    /// Parse `<package>/<namespace>/<name>` or `<package>/<name>` (the namespace is `msg`).
+    fn parse(type_name: &'a str) -> Result<Self> {
+        let invalid = || RclRustError::InvalidTypeName(type_name.to_string());
+
+        let parts = type_name.split('/').collect::<Vec<_>>();
+        let (package, namespace, name) = match parts.as_slice() {
+            [package, name] => (*package, "msg", *name),
+            [package, namespace, name] => (*package, *namespace, *name),
+            _ => return Err(invalid().into()),
+        };
+        if [package, namespace, name].iter().any(|s| s.is_empty()) {
+            return Err(invalid().into());
+        }
+
+        Ok(Self {
+            package,
+            namespace,
+            name,
+        })
+    }
+
+    fn library_name(&self) -> String {
+        format!("{}__rosidl_typesupport_c", self.package)
+    }
+
+    fn symbol_name(&self) -> String {
+        format!(
+            "rosidl_typesupport_c__get_message_type_support_handle__{}__{}__{}",
+            self.package, self.namespace, self.name
+        )
+    }
+}
+
+fn library_path(library_name: &str) -> PathBuf {
+    let file_name = libloading::library_filename(library_name);
+
+    env::var_os("AMENT_PREFIX_PATH")
+        .and_then(|prefixes| {
+            env::split_paths(&prefixes)
+                .map(|prefix| prefix.join("lib").join(&file_name))
+                .find(|path| path.exists())
+        })
+        .unwrap_or_else(|| file_name.into())
+}
+
+/// Get the message type support of `type_name` (e.g. `"std_msgs/msg/String"`).
+pub fn get_message_type_support(type_name: &str) -> Result<*const c_void> {
+    let type_name = TypeName::parse(type_name)?;
+    let library_name = type_name.library_name();
+    let symbol_name = type_name.symbol_name();
+
+    let mut libraries = globals::get().type_support_libraries.lock().unwrap();
+    if !libraries.contains_key(&library_name) {
+        let path = library_path(&library_name);
+        let library = unsafe { Library::new(&path) }
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+        libraries.insert(library_name.clone(), library);
+    }
+
+    let type_support = unsafe {
+        let get_type_support: Symbol<'_, unsafe extern "C" fn() -> *const c_void> = libraries
+            [&library_name]
+            .get(symbol_name.as_bytes())
+            .with_context(|| format!("Failed to find {}", symbol_name))?;
+        get_type_support()
+    };
+    drop(libraries);
+
+    Ok(type_support)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_type_name() -> Result<()> {
+        assert_eq!(
+            TypeName::parse("std_msgs/msg/String")?,
+            TypeName {
+                package: "std_msgs",
+                namespace: "msg",
+                name: "String"
+            }
+        );
+        assert_eq!(
+            TypeName::parse("std_msgs/String")?,
+            TypeName::parse("std_msgs/msg/String")?
+        );
+        assert!(TypeName::parse("String").is_err());
+        assert!(TypeName::parse("std_msgs//String").is_err());
+        assert!(TypeName::parse("a/b/c/d").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn type_name_symbol_name() -> Result<()> {
+        let type_name = TypeName::parse("sensor_msgs/msg/Image")?;
+        assert_eq!(
+            type_name.library_name(),
+            "sensor_msgs__rosidl_typesupport_c"
+        );
+        assert_eq!(
+            type_name.symbol_name(),
+            "rosidl_typesupport_c__get_message_type_support_handle__sensor_msgs__msg__Image"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_message_type_support_of_int32() -> Result<()> {
+        assert!(!get_message_type_support("std_msgs/msg/Int32")?.is_null());
+        assert!(get_message_type_support("std_msgs/msg/NotExist").is_err());
+
+        Ok(())
+    }
+}
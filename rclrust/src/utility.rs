@@ -26,7 +26,27 @@ pub fn init() -> Result<Context> {
 /// assert!(ctx.is_valid());
 /// ```
 pub fn init_with_options(init_options: InitOptions) -> Result<Context> {
-    Context::new(std::env::args().collect::<Vec<_>>(), init_options)
+    init_with_args(std::env::args().collect::<Vec<_>>(), init_options)
+}
+
+/// Initialize rclrust context with the given command line arguments instead of the ones of the
+/// process
+///
+/// # Examples
+///
+/// ```
+/// use rclrust::InitOptions;
+///
+/// let args = ["my_node", "--ros-args", "-p", "param:=1"];
+/// let ctx = rclrust::init_with_args(
+///     args.iter().map(|s| s.to_string()).collect(),
+///     InitOptions::new().unwrap(),
+/// )
+/// .unwrap();
+/// assert!(ctx.is_valid());
+/// ```
+pub fn init_with_args(args: Vec<String>, init_options: InitOptions) -> Result<Context> {
+    Context::new(args, init_options)
 }
 
 /// Check rclrust's status.